@@ -0,0 +1,71 @@
+use std::fs;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use advent_of_code_2021::*;
+
+fn read_input(day: u32) -> String {
+    let path = format!("input/2021/day{}.txt", day);
+    let input =
+        fs::read_to_string(&path).unwrap_or_else(|e| panic!("could not read {}: {}", path, e));
+
+    // Strip the trailing newline the same way the runners do
+    input.trim_end_matches('\n').to_string()
+}
+
+/// Benchmark the generator and both parts of a day as separate functions in one group, the parts
+/// are measured against input that has already been generated.
+macro_rules! bench_day {
+    ($name:ident, $day:literal, $module:ident) => {
+        fn $name(c: &mut Criterion) {
+            let input = read_input($day);
+            let generated = $module::input_generator(&input).unwrap();
+
+            let mut group = c.benchmark_group(stringify!($module));
+            group.bench_function("generator", |b| {
+                b.iter(|| $module::input_generator(black_box(&input)))
+            });
+            group.bench_function("part1", |b| {
+                b.iter(|| $module::solve_part1(black_box(&generated)))
+            });
+            group.bench_function("part2", |b| {
+                b.iter(|| $module::solve_part2(black_box(&generated)))
+            });
+            group.finish();
+        }
+    };
+}
+
+bench_day!(bench_day1, 1, day1);
+bench_day!(bench_day2, 2, day2);
+bench_day!(bench_day3, 3, day3);
+bench_day!(bench_day4, 4, day4);
+bench_day!(bench_day5, 5, day5);
+bench_day!(bench_day6, 6, day6);
+bench_day!(bench_day7, 7, day7);
+bench_day!(bench_day8, 8, day8);
+bench_day!(bench_day9, 9, day9);
+bench_day!(bench_day10, 10, day10);
+bench_day!(bench_day11, 11, day11);
+bench_day!(bench_day12, 12, day12);
+bench_day!(bench_day13, 13, day13);
+bench_day!(bench_day14, 14, day14);
+
+criterion_group!(
+    benches,
+    bench_day1,
+    bench_day2,
+    bench_day3,
+    bench_day4,
+    bench_day5,
+    bench_day6,
+    bench_day7,
+    bench_day8,
+    bench_day9,
+    bench_day10,
+    bench_day11,
+    bench_day12,
+    bench_day13,
+    bench_day14
+);
+criterion_main!(benches);
@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use advent_of_code_2021::day12;
+
+fuzz_target!(|input: &str| {
+    // Rejecting the input is fine, only a panic is a crash
+    let _ = day12::input_generator(input);
+});
@@ -0,0 +1,113 @@
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+
+/// The result of solving a single part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Integer(i64),
+    /// An integer that does not fit in an `i64`.
+    BigInteger(u128),
+    /// A textual answer, which may span multiple lines.
+    Text(String),
+}
+
+impl Answer {
+    /// Create a textual answer from rendered output, trailing whitespace is dropped from every line
+    /// so the answer does not depend on how wide the rendering happened to be.
+    pub fn from_rendered(rendered: &str) -> Self {
+        Self::Text(
+            rendered
+                .lines()
+                .map(str::trim_end)
+                .collect::<Vec<_>>()
+                .join("\n")
+                .trim_end()
+                .to_string(),
+        )
+    }
+
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Self::Text(text) if text.contains('\n'))
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Integer(value) => write!(f, "{}", value),
+            Self::BigInteger(value) => write!(f, "{}", value),
+            Self::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+macro_rules! impl_from_signed {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Self::Integer(value as i64)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_from_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    match i64::try_from(value) {
+                        Ok(value) => Self::Integer(value),
+                        Err(_) => Self::BigInteger(value as u128),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_signed!(i8, i16, i32, i64, isize);
+impl_from_unsigned!(u8, u16, u32, u64, u128, usize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_integers() {
+        assert_eq!(Answer::Integer(-3), Answer::from(-3isize));
+        assert_eq!(Answer::Integer(42), Answer::from(42usize));
+        assert_eq!(Answer::BigInteger(u64::MAX as u128), Answer::from(u64::MAX));
+    }
+
+    #[test]
+    fn test_from_rendered() {
+        let answer = Answer::from_rendered("# #  \n ## \n    \n");
+
+        assert_eq!(Answer::Text("# #\n ##".to_string()), answer);
+        assert!(answer.is_multiline());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            "1757714216975",
+            Answer::from(1757714216975usize).to_string()
+        );
+        assert_eq!("ABC", Answer::from("ABC").to_string());
+    }
+}
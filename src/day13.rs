@@ -1,145 +1,272 @@
 use std::cmp;
-use std::collections::HashSet;
 use std::fmt::Display;
-use std::iter::FromIterator;
 
-#[derive(Debug, Eq, PartialEq, Hash)]
+use crate::answer::Answer;
+use crate::error::{parse_number, ParseError};
+use crate::grid::Grid;
+use crate::visualize::{Color, Frame, Visualize};
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct Point {
-    x: i32,
-    y: i32,
+    x: usize,
+    y: usize,
 }
 
-#[derive(Debug)]
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Instruction {
-    X(i32),
-    Y(i32),
+    X(usize),
+    Y(usize),
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction::X(column_index) => write!(f, "fold along x={}", column_index),
+            Instruction::Y(row_index) => write!(f, "fold along y={}", row_index),
+        }
+    }
 }
 
-type Input = (Vec<Point>, Vec<Instruction>);
+pub type Input = (Vec<Point>, Vec<Instruction>);
+
+/// Every coordinate and fold line is below this, which keeps the paper small enough to hold.
+const MAX_SIZE: usize = 4096;
 
 pub struct Paper {
-    data: HashSet<Point>,
+    data: Grid<bool>,
 }
 
 impl Paper {
-    pub fn new(data: HashSet<Point>) -> Self {
+    pub fn new(points: &[Point]) -> Self {
+        let width = points
+            .iter()
+            .fold(0, |acc, point| cmp::max(acc, point.x + 1));
+        let height = points
+            .iter()
+            .fold(0, |acc, point| cmp::max(acc, point.y + 1));
+
+        let mut data = Grid::filled(width, height, false);
+
+        for point in points {
+            data[(point.x, point.y)] = true;
+        }
+
         Self { data }
     }
 
-    pub fn fold(self, instruction: Instruction) -> Self {
-        let mut new_points = HashSet::<Point>::new();
+    pub fn fold(self, instruction: &Instruction) -> Self {
+        let (width, height) = match *instruction {
+            Instruction::X(column_index) => (column_index, self.height()),
+            Instruction::Y(row_index) => (self.width(), row_index),
+        };
+
+        let mut data = Grid::filled(width, height, false);
 
-        for mut point in self.data.into_iter() {
-            match instruction {
-                Instruction::X(column_index) => {
-                    if point.x > column_index {
-                        point.x = column_index - (point.x - column_index)
-                    }
+        for ((x, y), _) in self.data.iter().filter(|(_, dot)| **dot) {
+            // Mirror the points past the fold line, anything that would end up beyond the other
+            // edge of the paper is dropped
+            let position = match *instruction {
+                Instruction::X(column_index) if x > column_index => {
+                    column_index.checked_sub(x - column_index).map(|x| (x, y))
                 }
-                Instruction::Y(row_index) => {
-                    if point.y > row_index {
-                        point.y = row_index - (point.y - row_index)
-                    }
+                Instruction::Y(row_index) if y > row_index => {
+                    row_index.checked_sub(y - row_index).map(|y| (x, y))
                 }
-            }
+                _ => Some((x, y)),
+            };
 
-            new_points.insert(point);
+            if let Some(dot) = position.and_then(|position| data.get_mut(position)) {
+                *dot = true;
+            }
         }
 
-        Self::new(new_points)
+        Self { data }
     }
 
-    pub fn width(&self) -> i32 {
-        self.data
-            .iter()
-            .fold(0, |acc, point| cmp::max(acc, point.x))
-            + 1
+    pub fn width(&self) -> usize {
+        self.data.width()
     }
 
-    pub fn height(&self) -> i32 {
-        self.data
-            .iter()
-            .fold(0, |acc, point| cmp::max(acc, point.y))
-            + 1
+    pub fn height(&self) -> usize {
+        self.data.height()
+    }
+
+    pub fn dots(&self) -> usize {
+        self.data.values().filter(|dot| **dot).count()
     }
 }
 
 impl Display for Paper {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for y in 0..self.height() {
-            for x in 0..self.width() {
-                write!(
-                    f,
-                    "{}",
-                    if self.data.contains(&Point { x, y }) {
-                        "#"
-                    } else {
-                        " "
-                    }
-                )?;
-            }
+        write!(f, "{}", self.data.map(|dot| if *dot { '#' } else { ' ' }))
+    }
+}
+
+impl Visualize for Input {
+    /// The paper before folding and after every fold, with the dots in white.
+    fn frames(&self) -> Vec<Frame> {
+        let render = |paper: &Paper| {
+            paper
+                .data
+                .map(|&dot| if dot { Color::WHITE } else { Color::BLACK })
+        };
 
-            writeln!(f)?;
+        let mut paper = Paper::new(&self.0);
+        let mut frames = vec![render(&paper)];
+
+        for instruction in &self.1 {
+            paper = paper.fold(instruction);
+            frames.push(render(&paper));
         }
-        write!(f, "")
+
+        frames
     }
 }
 
-fn parse_input(input: &str) -> Input {
-    let (points_str, instructions_str) = input.split_once("\n\n").unwrap();
+fn parse_coordinate(input: &str, token: &str) -> Result<usize, ParseError> {
+    let coordinate = parse_number(input, token)?;
+
+    if coordinate >= MAX_SIZE {
+        return Err(ParseError::unexpected(
+            input,
+            token,
+            "a coordinate below 4096",
+        ));
+    }
+
+    Ok(coordinate)
+}
+
+#[aoc_generator(day13)]
+pub fn input_generator(input: &str) -> Result<Input, ParseError> {
+    let (points_str, instructions_str) = input.split_once("\n\n").ok_or_else(|| {
+        ParseError::unexpected_end(input, "fold instructions after an empty line")
+    })?;
 
     let points = points_str
         .lines()
-        .map(|line| line.split_once(',').unwrap())
-        .map(|(x_str, y_str)| Point {
-            x: x_str.parse::<i32>().unwrap(),
-            y: y_str.parse::<i32>().unwrap(),
+        .map(|line| {
+            let (x_str, y_str) = line
+                .split_once(',')
+                .ok_or_else(|| ParseError::unexpected(input, line, "a point like `x,y`"))?;
+
+            Ok(Point {
+                x: parse_coordinate(input, x_str)?,
+                y: parse_coordinate(input, y_str)?,
+            })
         })
-        .collect::<Vec<Point>>();
+        .collect::<Result<Vec<Point>, ParseError>>()?;
 
     let instructions = instructions_str
         .lines()
-        .map(|line| line.split(' ').nth(2).unwrap().split_once('=').unwrap())
-        .map(|(direction, amount_str)| {
-            let amount = amount_str.parse::<i32>().unwrap();
+        .map(|line| {
+            let (direction, amount_str) = line
+                .strip_prefix("fold along ")
+                .and_then(|fold| fold.split_once('='))
+                .ok_or_else(|| {
+                    ParseError::unexpected(input, line, "`fold along x=..` or `y=..`")
+                })?;
+            let amount = parse_coordinate(input, amount_str)?;
 
             match direction {
-                "x" => Instruction::X(amount),
-                "y" => Instruction::Y(amount),
-                _ => unreachable!(),
+                "x" => Ok(Instruction::X(amount)),
+                "y" => Ok(Instruction::Y(amount)),
+                _ => Err(ParseError::unexpected(input, direction, "x or y")),
             }
         })
-        .collect::<Vec<Instruction>>();
+        .collect::<Result<Vec<Instruction>, ParseError>>()?;
 
-    (points, instructions)
+    Ok((points, instructions))
 }
 
 #[aoc(day13, part1)]
-pub fn solve_part1(input: &str) -> usize {
-    let data = parse_input(input);
-    let mut paper = Paper::new(HashSet::<Point>::from_iter(data.0.into_iter()));
+pub fn solve_part1(input: &Input) -> usize {
+    let mut paper = Paper::new(&input.0);
 
-    for instruction in data.1.into_iter().take(1) {
+    for instruction in input.1.iter().take(1) {
         paper = paper.fold(instruction)
     }
 
-    paper.data.len()
+    paper.dots()
 }
 
 #[aoc(day13, part2)]
-pub fn solve_part2(input: &str) -> usize {
-    let data = parse_input(input);
-    let mut paper = Paper::new(HashSet::<Point>::from_iter(data.0.into_iter()));
+pub fn solve_part2(input: &Input) -> Answer {
+    let mut paper = Paper::new(&input.0);
 
-    for instruction in data.1 {
+    for instruction in &input.1 {
         paper = paper.fold(instruction);
+    }
 
-        if paper.height() == 6 {
-            break;
-        }
+    Answer::from_rendered(&paper.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn input() -> impl Strategy<Value = Input> {
+        let point = (0..MAX_SIZE, 0..MAX_SIZE).prop_map(|(x, y)| Point { x, y });
+        let instruction = prop_oneof![
+            (0..MAX_SIZE).prop_map(Instruction::X),
+            (0..MAX_SIZE).prop_map(Instruction::Y)
+        ];
+
+        (
+            prop::collection::vec(point, 1..20),
+            prop::collection::vec(instruction, 1..10),
+        )
+    }
+
+    fn render(input: &Input) -> String {
+        let points = input.0.iter().map(|point| point.to_string());
+        let instructions = input.1.iter().map(|instruction| instruction.to_string());
+
+        format!(
+            "{}\n\n{}",
+            points.collect::<Vec<_>>().join("\n"),
+            instructions.collect::<Vec<_>>().join("\n")
+        )
     }
 
-    println!("{}", paper);
+    #[test]
+    fn test_invalid_fold() {
+        let input = "6,10\n0,14\n\nfold along y=7\nfold along z=5";
 
-    0
+        assert_eq!(
+            "line 5, column 12: expected x or y, found `z`",
+            input_generator(input).unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_paper_too_large() {
+        assert_eq!(
+            "line 3, column 14: expected a coordinate below 4096, found `18446744073709551615`",
+            input_generator("0,0\n\nfold along x=18446744073709551615")
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(
+            "line 1, column 3: expected a coordinate below 4096, found `4096`",
+            input_generator("0,4096\n\nfold along y=1")
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_input_round_trip(input in input()) {
+            prop_assert_eq!(Ok(input.clone()), input_generator(&render(&input)));
+        }
+    }
 }
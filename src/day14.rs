@@ -3,37 +3,99 @@ use std::str::FromStr;
 
 use itertools::Itertools;
 
+use crate::error::ParseError;
+
 type PolymerTemplate = Vec<char>;
 type PairInsertionRules = HashMap<(char, char), char>;
 
-struct Input(PolymerTemplate, PairInsertionRules);
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input(PolymerTemplate, PairInsertionRules);
+
+impl Input {
+    pub fn template(&self) -> &[char] {
+        &self.0
+    }
+
+    pub fn rules(&self) -> &PairInsertionRules {
+        &self.1
+    }
+}
 
 impl FromStr for Input {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut lines = s.lines();
 
-        let polymer_template = lines.next().unwrap().chars().collect::<PolymerTemplate>();
+        let template_line = lines
+            .next()
+            .filter(|line| !line.is_empty())
+            .ok_or_else(|| ParseError::unexpected_end(s, "a polymer template"))?;
+        let polymer_template = template_line.chars().collect::<PolymerTemplate>();
+
+        if let Some(line) = lines.next().filter(|line| !line.is_empty()) {
+            return Err(ParseError::unexpected(s, line, "an empty line"));
+        }
+
         let pair_insertion_rules = lines
-            .skip(1)
-            .into_iter()
-            .map(|line| line.split_once(" -> ").unwrap())
-            .map(|(pair, insertion)| {
-                let mut pair_chars = pair.chars();
-
-                (
-                    (pair_chars.next().unwrap(), pair_chars.next().unwrap()),
-                    insertion.chars().next().unwrap(),
-                )
+            .map(|line| {
+                let rule = line.split_once(" -> ").and_then(|(pair, insertion)| {
+                    match (
+                        pair.chars().collect_tuple(),
+                        insertion.chars().collect_tuple(),
+                    ) {
+                        (Some(pair), Some((insertion,))) => Some((pair, insertion)),
+                        _ => None,
+                    }
+                });
+
+                rule.ok_or_else(|| ParseError::unexpected(s, line, "a rule like `AB -> C`"))
             })
-            .collect::<PairInsertionRules>();
+            .collect::<Result<PairInsertionRules, ParseError>>()?;
+
+        // Every pair that can occur while growing the polymer needs a rule
+        let elements = polymer_template
+            .iter()
+            .chain(pair_insertion_rules.values())
+            .unique()
+            .collect::<Vec<_>>();
+
+        let missing = elements
+            .iter()
+            .cartesian_product(elements.iter())
+            .find(|&(a, b)| !pair_insertion_rules.contains_key(&(**a, **b)));
+
+        if let Some((&&a, &&b)) = missing {
+            // Point at the pair in the template, or else at the rule that brings in the element
+            // that isn't in the template
+            let token = template_line
+                .char_indices()
+                .tuple_windows()
+                .find(|&((_, x), (_, y))| (x, y) == (a, b))
+                .map(|((index, x), (_, y))| {
+                    &template_line[index..index + x.len_utf8() + y.len_utf8()]
+                })
+                .or_else(|| {
+                    s.lines().skip(2).find(|line| {
+                        [a, b].iter().any(|&element| {
+                            !polymer_template.contains(&element) && line.ends_with(element)
+                        })
+                    })
+                })
+                .unwrap_or(template_line);
+
+            return Err(ParseError::unexpected(
+                s,
+                token,
+                "an insertion rule for every pair of elements",
+            ));
+        }
 
         Ok(Self(polymer_template, pair_insertion_rules))
     }
 }
 
-fn create_polymer(template: PolymerTemplate, rules: PairInsertionRules, steps: usize) -> usize {
+fn create_polymer(template: &[char], rules: &PairInsertionRules, steps: usize) -> usize {
     let mut counts = (0..steps)
         .fold(
             template.iter().tuple_windows::<(_, _)>().counts(),
@@ -68,69 +130,79 @@ fn create_polymer(template: PolymerTemplate, rules: PairInsertionRules, steps: u
     max - min
 }
 
-#[aoc(day14, part1)]
-pub fn solve_part1(raw: &str) -> usize {
-    let input = raw.parse::<Input>().unwrap();
+#[aoc_generator(day14)]
+pub fn input_generator(input: &str) -> Result<Input, ParseError> {
+    input.parse()
+}
 
-    create_polymer(input.0, input.1, 10)
+#[aoc(day14, part1)]
+pub fn solve_part1(input: &Input) -> usize {
+    create_polymer(&input.0, &input.1, 10)
 }
 
 #[aoc(day14, part2)]
-pub fn solve_part2(raw: &str) -> usize {
-    let input = raw.parse::<Input>().unwrap();
-
-    create_polymer(input.0, input.1, 40)
+pub fn solve_part2(input: &Input) -> usize {
+    create_polymer(&input.0, &input.1, 40)
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
-    #[test]
-    fn test_example_part1() {
-        let input = r#"NNCB
-
-CH -> B
-HH -> N
-CB -> H
-NH -> C
-HB -> C
-HC -> B
-HN -> C
-NN -> C
-BH -> H
-NC -> B
-NB -> B
-BN -> B
-BB -> N
-BC -> B
-CC -> N
-CN -> C"#;
-
-        assert_eq!(1588, solve_part1(input))
+    /// A template of up to four elements with a rule for every pair of them.
+    fn input() -> impl Strategy<Value = Input> {
+        (1..=4u8).prop_flat_map(|count| {
+            let element = (0..count).prop_map(|element| (b'A' + element) as char);
+            let pairs = (0..count)
+                .flat_map(|a| (0..count).map(move |b| ((b'A' + a) as char, (b'A' + b) as char)))
+                .collect::<Vec<_>>();
+
+            (
+                prop::collection::vec(element.clone(), 1..10),
+                prop::collection::vec(element, pairs.len()),
+            )
+                .prop_map(move |(template, insertions)| {
+                    Input(template, pairs.iter().copied().zip(insertions).collect())
+                })
+        })
+    }
+
+    fn render(input: &Input) -> String {
+        let rules = input
+            .rules()
+            .iter()
+            .map(|(&(a, b), insertion)| format!("{}{} -> {}", a, b, insertion));
+
+        format!(
+            "{}\n\n{}",
+            input.template().iter().collect::<String>(),
+            rules.collect::<Vec<_>>().join("\n")
+        )
     }
 
     #[test]
-    fn test_example_part2() {
-        let input = r#"NNCB
-
-CH -> B
-HH -> N
-CB -> H
-NH -> C
-HB -> C
-HC -> B
-HN -> C
-NN -> C
-BH -> H
-NC -> B
-NB -> B
-BN -> B
-BB -> N
-BC -> B
-CC -> N
-CN -> C"#;
-
-        assert_eq!(2188189693529, solve_part2(input))
+    fn test_missing_rule() {
+        assert_eq!(
+            "line 1, column 2: expected an insertion rule for every pair of elements, found `BA`",
+            input_generator("ABA\n\nAA -> A\nAB -> A\nBB -> A")
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(
+            "line 4, column 1: expected an insertion rule for every pair of elements, found \
+             `AA -> C`",
+            input_generator("AA\n\nCC -> A\nAA -> C")
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_input_round_trip(input in input()) {
+            prop_assert_eq!(Ok(input.clone()), input_generator(&render(&input)));
+        }
     }
 }
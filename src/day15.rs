@@ -0,0 +1,91 @@
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::path::grid_path;
+
+type RiskMap = Grid<u32>;
+
+#[aoc_generator(day15)]
+pub fn input_generator(input: &str) -> Result<RiskMap, ParseError> {
+    let map = Grid::from_digits(input)?;
+
+    if map.width() == 0 {
+        return Err(ParseError::unexpected_end(input, "a map of risk levels"));
+    }
+
+    if let Some(index) = input.find('0') {
+        return Err(ParseError::unexpected(
+            input,
+            &input[index..index + 1],
+            "a risk level from 1 to 9",
+        ));
+    }
+
+    Ok(map)
+}
+
+/// The lowest total risk of a path from the top left to the bottom right, the risk of the
+/// starting position is never entered so it does not count.
+fn lowest_total_risk(map: &RiskMap) -> u64 {
+    let goal = (map.width() - 1, map.height() - 1);
+
+    grid_path(map, (0, 0), goal, |&risk| Some(risk as u64))
+        .expect("every position can be reached")
+        .cost
+}
+
+/// Repeat the map `times` in both directions, every repetition to the right or down increases the
+/// risk levels by one, wrapping from 9 back to 1.
+fn tile(map: &RiskMap, times: usize) -> RiskMap {
+    let width = map.width() * times;
+    let height = map.height() * times;
+
+    let cells = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .map(|(x, y)| {
+            let risk = map[(x % map.width(), y % map.height())];
+            let increase = (x / map.width() + y / map.height()) as u32;
+
+            (risk + increase - 1) % 9 + 1
+        })
+        .collect();
+
+    Grid::new(width, height, cells)
+}
+
+#[aoc(day15, part1)]
+pub fn solve_part1(input: &RiskMap) -> u64 {
+    lowest_total_risk(input)
+}
+
+#[aoc(day15, part2)]
+pub fn solve_part2(input: &RiskMap) -> u64 {
+    lowest_total_risk(&tile(input, 5))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tile() {
+        let map = input_generator("8").unwrap();
+
+        assert_eq!("89\n91\n", tile(&map, 2).to_string());
+    }
+
+    #[test]
+    fn test_empty_map() {
+        assert_eq!(
+            "line 1, column 1: expected a map of risk levels, found end of input",
+            input_generator("").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_zero_risk() {
+        assert_eq!(
+            "line 2, column 2: expected a risk level from 1 to 9, found `0`",
+            input_generator("19\n10").unwrap_err().to_string()
+        );
+    }
+}
@@ -0,0 +1,482 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use crate::error::ParseError;
+
+/// The operation of an operator packet, from its type ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Sum,
+    Product,
+    Minimum,
+    Maximum,
+    GreaterThan,
+    LessThan,
+    EqualTo,
+}
+
+impl Operator {
+    fn from_type_id(type_id: u64) -> Option<Self> {
+        match type_id {
+            0 => Some(Self::Sum),
+            1 => Some(Self::Product),
+            2 => Some(Self::Minimum),
+            3 => Some(Self::Maximum),
+            5 => Some(Self::GreaterThan),
+            6 => Some(Self::LessThan),
+            7 => Some(Self::EqualTo),
+            _ => None,
+        }
+    }
+
+    pub fn type_id(&self) -> u64 {
+        match self {
+            Self::Sum => 0,
+            Self::Product => 1,
+            Self::Minimum => 2,
+            Self::Maximum => 3,
+            Self::GreaterThan => 5,
+            Self::LessThan => 6,
+            Self::EqualTo => 7,
+        }
+    }
+
+    fn is_comparison(&self) -> bool {
+        matches!(self, Self::GreaterThan | Self::LessThan | Self::EqualTo)
+    }
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Sum => "sum",
+            Self::Product => "product",
+            Self::Minimum => "minimum",
+            Self::Maximum => "maximum",
+            Self::GreaterThan => "greater than",
+            Self::LessThan => "less than",
+            Self::EqualTo => "equal to",
+        };
+
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Contents {
+    Literal(u64),
+    Operator {
+        operator: Operator,
+        packets: Vec<Packet>,
+    },
+}
+
+/// A packet of the BITS transmission with its sub-packets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Packet {
+    pub version: u8,
+    pub contents: Contents,
+}
+
+impl Packet {
+    pub fn literal(version: u8, value: u64) -> Self {
+        Self {
+            version,
+            contents: Contents::Literal(value),
+        }
+    }
+
+    pub fn operator(version: u8, operator: Operator, packets: Vec<Packet>) -> Self {
+        Self {
+            version,
+            contents: Contents::Operator { operator, packets },
+        }
+    }
+
+    /// The sum of the versions of this packet and all packets inside it.
+    pub fn version_sum(&self) -> u64 {
+        let nested = match &self.contents {
+            Contents::Literal(_) => 0,
+            Contents::Operator { packets, .. } => packets.iter().map(Packet::version_sum).sum(),
+        };
+
+        self.version as u64 + nested
+    }
+
+    /// The value of the expression the packet represents.
+    pub fn evaluate(&self) -> u64 {
+        let (operator, packets) = match &self.contents {
+            Contents::Literal(value) => return *value,
+            Contents::Operator { operator, packets } => (operator, packets),
+        };
+
+        let mut values = packets.iter().map(Packet::evaluate);
+
+        // Decoding guarantees the number of sub-packets, so the comparisons always have two values
+        match operator {
+            Operator::Sum => values.sum(),
+            Operator::Product => values.product(),
+            Operator::Minimum => values.min().unwrap_or_default(),
+            Operator::Maximum => values.max().unwrap_or_default(),
+            Operator::GreaterThan => (values.next() > values.next()) as u64,
+            Operator::LessThan => (values.next() < values.next()) as u64,
+            Operator::EqualTo => (values.next() == values.next()) as u64,
+        }
+    }
+
+    fn write_tree(&self, f: &mut Formatter<'_>, depth: usize) -> fmt::Result {
+        write!(f, "{:indent$}v{} ", "", self.version, indent = depth * 2)?;
+
+        match &self.contents {
+            Contents::Literal(value) => writeln!(f, "literal {}", value),
+            Contents::Operator { operator, packets } => {
+                writeln!(f, "{}", operator)?;
+
+                for packet in packets {
+                    packet.write_tree(f, depth + 1)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Print the packet as a tree, one packet per line with sub-packets indented below their operator.
+impl Display for Packet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.write_tree(f, 0)
+    }
+}
+
+/// How deep packets may be nested in each other, so reading and evaluating them can't run out of
+/// stack.
+const MAX_NESTING: usize = 100;
+
+/// Reads the bits of a hexadecimal transmission, reporting errors at the hex digit holding the bit.
+struct Reader<'a> {
+    source: &'a str,
+    bits: Vec<bool>,
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(source: &'a str) -> Result<Self, ParseError> {
+        let mut bits = Vec::with_capacity(source.len() * 4);
+
+        for (index, c) in source.char_indices() {
+            let digit = c.to_digit(16).ok_or_else(|| {
+                ParseError::unexpected(
+                    source,
+                    &source[index..index + c.len_utf8()],
+                    "a hexadecimal digit",
+                )
+            })?;
+
+            bits.extend((0..4).rev().map(|shift| digit >> shift & 1 == 1));
+        }
+
+        Ok(Self {
+            source,
+            bits,
+            position: 0,
+        })
+    }
+
+    fn read(&mut self, count: usize, expected: &'static str) -> Result<u64, ParseError> {
+        let bits = self
+            .bits
+            .get(self.position..self.position + count)
+            .ok_or_else(|| ParseError::unexpected_end(self.source, expected))?;
+
+        self.position += count;
+
+        Ok(bits.iter().fold(0, |value, &bit| value << 1 | bit as u64))
+    }
+
+    /// An error for the hex digit that holds the given bit.
+    fn error_at(&self, bit: usize, expected: &'static str) -> ParseError {
+        let index = (bit / 4).min(self.source.len().saturating_sub(1));
+
+        ParseError::unexpected(self.source, &self.source[index..index + 1], expected)
+    }
+
+    fn read_literal(&mut self) -> Result<u64, ParseError> {
+        let start = self.position;
+        let mut value = 0u64;
+
+        loop {
+            let group = self.read(5, "a literal value group")?;
+
+            if value.leading_zeros() < 4 {
+                return Err(self.error_at(start, "a literal value that fits in 64 bits"));
+            }
+
+            value = value << 4 | group & 0xf;
+
+            if group & 0x10 == 0 {
+                return Ok(value);
+            }
+        }
+    }
+
+    fn read_packet(&mut self, depth: usize) -> Result<Packet, ParseError> {
+        let start = self.position;
+
+        if depth > MAX_NESTING {
+            return Err(self.error_at(start, "packets nested at most 100 deep"));
+        }
+
+        let version = self.read(3, "a packet version")? as u8;
+        let type_id = self.read(3, "a packet type ID")?;
+
+        let operator = match Operator::from_type_id(type_id) {
+            Some(operator) => operator,
+            None => return Ok(Packet::literal(version, self.read_literal()?)),
+        };
+
+        let mut packets = vec![];
+
+        if self.read(1, "a length type ID")? == 0 {
+            let length = self.read(15, "a total length in bits")? as usize;
+            let end = self.position + length;
+
+            while self.position < end {
+                packets.push(self.read_packet(depth + 1)?);
+            }
+
+            if self.position != end {
+                return Err(self.error_at(end, "sub-packets that fill their total length"));
+            }
+        } else {
+            let count = self.read(11, "a number of sub-packets")?;
+
+            for _ in 0..count {
+                packets.push(self.read_packet(depth + 1)?);
+            }
+        }
+
+        if operator.is_comparison() && packets.len() != 2 {
+            return Err(self.error_at(start, "a comparison of exactly two sub-packets"));
+        }
+
+        if packets.is_empty() {
+            return Err(self.error_at(start, "an operator with sub-packets"));
+        }
+
+        Ok(Packet::operator(version, operator, packets))
+    }
+}
+
+/// Decode a hexadecimal BITS transmission into its outermost packet. Anything after the packet
+/// has to be zero padding.
+pub fn decode(transmission: &str) -> Result<Packet, ParseError> {
+    let mut reader = Reader::new(transmission)?;
+    let packet = reader.read_packet(0)?;
+
+    if let Some(offset) = reader.bits[reader.position..].iter().position(|&bit| bit) {
+        return Err(reader.error_at(reader.position + offset, "zero padding after the packet"));
+    }
+
+    Ok(packet)
+}
+
+impl FromStr for Packet {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        decode(s)
+    }
+}
+
+#[aoc_generator(day16)]
+pub fn input_generator(input: &str) -> Result<Packet, ParseError> {
+    decode(input)
+}
+
+#[aoc(day16, part1)]
+pub fn solve_part1(input: &Packet) -> u64 {
+    input.version_sum()
+}
+
+#[aoc(day16, part2)]
+pub fn solve_part2(input: &Packet) -> u64 {
+    input.evaluate()
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn packet() -> impl Strategy<Value = Packet> {
+        let literal =
+            (0..8u8, any::<u64>()).prop_map(|(version, value)| Packet::literal(version, value));
+        let operator = prop::sample::select(vec![
+            Operator::Sum,
+            Operator::Product,
+            Operator::Minimum,
+            Operator::Maximum,
+            Operator::GreaterThan,
+            Operator::LessThan,
+            Operator::EqualTo,
+        ]);
+
+        literal.prop_recursive(4, 64, 4, move |packet| {
+            (
+                0..8u8,
+                operator.clone(),
+                prop::collection::vec(packet, 1..=4),
+            )
+                .prop_map(|(version, operator, mut packets)| {
+                    if operator.is_comparison() {
+                        packets.resize(2, packets[0].clone());
+                    }
+
+                    Packet::operator(version, operator, packets)
+                })
+        })
+    }
+
+    fn push_bits(bits: &mut String, value: u64, count: usize) {
+        bits.extend(
+            (0..count)
+                .rev()
+                .map(|bit| if value >> bit & 1 == 1 { '1' } else { '0' }),
+        );
+    }
+
+    /// The bits of the packet, giving the sub-packets by their total length when it fits and
+    /// `by_length` is set, and by their number otherwise.
+    fn encode(packet: &Packet, by_length: bool) -> String {
+        let mut bits = String::new();
+        push_bits(&mut bits, packet.version as u64, 3);
+
+        match &packet.contents {
+            Contents::Literal(value) => {
+                push_bits(&mut bits, 4, 3);
+
+                let groups = ((u64::BITS - value.leading_zeros()) as usize)
+                    .div_ceil(4)
+                    .max(1);
+                for group in (0..groups).rev() {
+                    push_bits(&mut bits, (group > 0) as u64, 1);
+                    push_bits(&mut bits, value >> (4 * group) & 0xf, 4);
+                }
+            }
+            Contents::Operator { operator, packets } => {
+                push_bits(&mut bits, operator.type_id(), 3);
+
+                let contents = packets
+                    .iter()
+                    .map(|packet| encode(packet, by_length))
+                    .collect::<String>();
+
+                if by_length && contents.len() < 1 << 15 {
+                    push_bits(&mut bits, 0, 1);
+                    push_bits(&mut bits, contents.len() as u64, 15);
+                } else {
+                    push_bits(&mut bits, 1, 1);
+                    push_bits(&mut bits, packets.len() as u64, 11);
+                }
+
+                bits.push_str(&contents);
+            }
+        }
+
+        bits
+    }
+
+    #[test]
+    fn test_decode_literal() {
+        assert_eq!(Ok(Packet::literal(6, 2021)), decode("D2FE28"));
+    }
+
+    #[test]
+    fn test_decode_operators() {
+        // Sub-packets given by their total length in bits and by their number
+        assert_eq!(
+            Ok(Packet::operator(
+                1,
+                Operator::LessThan,
+                vec![Packet::literal(6, 10), Packet::literal(2, 20)]
+            )),
+            decode("38006F45291200")
+        );
+        assert_eq!(
+            Ok(Packet::operator(
+                7,
+                Operator::Maximum,
+                vec![
+                    Packet::literal(2, 1),
+                    Packet::literal(4, 2),
+                    Packet::literal(1, 3)
+                ]
+            )),
+            decode("EE00D40C823060")
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let packet = decode("8A004A801A8002F478").unwrap();
+
+        assert_eq!(
+            "v4 minimum\n  v1 minimum\n    v5 minimum\n      v6 literal 15\n",
+            packet.to_string()
+        );
+    }
+
+    #[test]
+    fn test_malformed_transmissions() {
+        assert_eq!(
+            "line 1, column 3: expected a hexadecimal digit, found `x`",
+            decode("D2xE28").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "line 1, column 5: expected a literal value group, found end of input",
+            decode("D2FE").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "line 1, column 6: expected zero padding after the packet, found `9`",
+            decode("D2FE29").unwrap_err().to_string()
+        );
+    }
+
+    /// The transmission of the bits, padded with zeros to whole hex digits.
+    fn hex(bits: &str) -> String {
+        let padding = "0".repeat((4 - bits.len() % 4) % 4);
+
+        (bits.to_string() + &padding)
+            .as_bytes()
+            .chunks(4)
+            .map(|nibble| {
+                let nibble = std::str::from_utf8(nibble).unwrap();
+                format!("{:X}", u8::from_str_radix(nibble, 2).unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_nesting_limit() {
+        // Sum packets with one sub-packet each around a literal, the sub-packet given by number
+        let nested = |depth: usize| hex(&("000000100000000001".repeat(depth) + "00010000001"));
+
+        assert_eq!(
+            Ok(1),
+            decode(&nested(MAX_NESTING)).map(|packet| packet.evaluate())
+        );
+        assert_eq!(
+            "line 1, column 455: expected packets nested at most 100 deep, found `4`",
+            decode(&nested(200_000)).unwrap_err().to_string()
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_packet_round_trip(packet in packet(), by_length in any::<bool>()) {
+            prop_assert_eq!(Ok(packet.clone()), decode(&hex(&encode(&packet, by_length))));
+        }
+    }
+}
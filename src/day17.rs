@@ -0,0 +1,286 @@
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::error::{parse_number, ParseError};
+
+pub type Vector = (i64, i64);
+
+/// The positions a probe launched from the origin passes through, one per step.
+#[derive(Debug, Clone)]
+pub struct Trajectory {
+    position: Vector,
+    velocity: Vector,
+}
+
+impl Trajectory {
+    pub fn new(velocity: Vector) -> Self {
+        Self {
+            position: (0, 0),
+            velocity,
+        }
+    }
+
+    pub fn velocity(&self) -> Vector {
+        self.velocity
+    }
+}
+
+impl Iterator for Trajectory {
+    type Item = Vector;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (vx, vy) = self.velocity;
+
+        self.position = (self.position.0 + vx, self.position.1 + vy);
+        // Drag slows the probe down towards zero, gravity keeps pulling it down
+        self.velocity = (vx - vx.signum(), vy - 1);
+
+        Some(self.position)
+    }
+}
+
+/// The area the probe has to hit. Parsed bounds fit in an `i32`, so the velocities that could hit
+/// it and the positions they pass through fit in an `i64`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TargetArea {
+    pub x: RangeInclusive<i64>,
+    pub y: RangeInclusive<i64>,
+}
+
+impl TargetArea {
+    pub fn contains(&self, (x, y): Vector) -> bool {
+        self.x.contains(&x) && self.y.contains(&y)
+    }
+
+    /// Whether a probe at the position, moving with the velocity, can never reach the area again.
+    fn is_past(&self, (x, y): Vector, (vx, vy): Vector) -> bool {
+        (vy < 0 && y < *self.y.start())
+            || (vx >= 0 && x > *self.x.end())
+            || (vx <= 0 && x < *self.x.start())
+    }
+
+    /// Launch a probe and return the highest point it reaches, or `None` when it misses.
+    pub fn launch(&self, velocity: Vector) -> Option<i64> {
+        let mut trajectory = Trajectory::new(velocity);
+        let mut apex = 0;
+
+        loop {
+            let position = trajectory.next()?;
+            apex = apex.max(position.1);
+
+            if self.contains(position) {
+                return Some(apex);
+            }
+
+            if self.is_past(position, trajectory.velocity()) {
+                return None;
+            }
+        }
+    }
+
+    /// The horizontal and vertical velocities that can possibly hit the area.
+    ///
+    /// Drag stops a probe after travelling `vx * (vx + 1) / 2`, so a slower probe never gets to the
+    /// area and a faster one overshoots it after the first step. Going up, a probe comes back down
+    /// through `y = 0` with its initial speed plus one, so it skips an area below `y = -vy - 1`.
+    pub fn velocity_bounds(&self) -> (RangeInclusive<i64>, RangeInclusive<i64>) {
+        let (x_min, x_max) = (*self.x.start(), *self.x.end());
+        let (y_min, y_max) = (*self.y.start(), *self.y.end());
+
+        let reach = |distance: i64| {
+            // The smallest speed whose stopping distance reaches `distance`
+            let mut speed = ((2 * distance) as f64).sqrt() as i64;
+            while speed * (speed + 1) / 2 < distance {
+                speed += 1;
+            }
+            speed
+        };
+
+        let vx = if x_min > 0 {
+            reach(x_min)..=x_max
+        } else if x_max < 0 {
+            x_min..=-reach(-x_max)
+        } else {
+            x_min..=x_max
+        };
+
+        let vy = if y_max < 0 {
+            y_min..=-y_min - 1
+        } else {
+            y_min.min(0)..=y_max.max(-y_min)
+        };
+
+        (vx, vy)
+    }
+
+    /// Every velocity that hits the area with the apex of its trajectory.
+    pub fn hits(&self) -> impl Iterator<Item = (Vector, i64)> + '_ {
+        let (vx, vy) = self.velocity_bounds();
+
+        vx.flat_map(move |vx| vy.clone().map(move |vy| (vx, vy)))
+            .filter_map(move |velocity| self.launch(velocity).map(|apex| (velocity, apex)))
+    }
+}
+
+fn parse_range(source: &str, token: &str, axis: &str) -> Result<RangeInclusive<i64>, ParseError> {
+    let (start, end) = token
+        .strip_prefix(axis)
+        .and_then(|range| range.strip_prefix('='))
+        .and_then(|range| range.split_once(".."))
+        .ok_or_else(|| ParseError::unexpected(source, token, "a range like `x=20..30`"))?;
+
+    let range =
+        parse_number::<i32>(source, start)? as i64..=parse_number::<i32>(source, end)? as i64;
+
+    if range.is_empty() {
+        return Err(ParseError::unexpected(
+            source,
+            token,
+            "a range from the lowest to the highest",
+        ));
+    }
+
+    Ok(range)
+}
+
+impl FromStr for TargetArea {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x, y) = s
+            .strip_prefix("target area: ")
+            .and_then(|ranges| ranges.split_once(", "))
+            .ok_or_else(|| {
+                ParseError::unexpected(s, s, "a target like `target area: x=20..30, y=-10..-5`")
+            })?;
+
+        Ok(Self {
+            x: parse_range(s, x, "x")?,
+            y: parse_range(s, y, "y")?,
+        })
+    }
+}
+
+#[aoc_generator(day17)]
+pub fn input_generator(input: &str) -> Result<TargetArea, ParseError> {
+    input.parse()
+}
+
+#[aoc(day17, part1)]
+pub fn solve_part1(input: &TargetArea) -> i64 {
+    input.hits().map(|(_, apex)| apex).max().unwrap_or_default()
+}
+
+#[aoc(day17, part2)]
+pub fn solve_part2(input: &TargetArea) -> usize {
+    input.hits().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn target_area() -> impl Strategy<Value = TargetArea> {
+        let range =
+            (any::<i32>(), any::<i32>()).prop_map(|(a, b)| a.min(b) as i64..=a.max(b) as i64);
+
+        (range.clone(), range).prop_map(|(x, y)| TargetArea { x, y })
+    }
+
+    const EXAMPLE: &str = "target area: x=20..30, y=-10..-5";
+
+    #[test]
+    fn test_trajectory() {
+        let positions = Trajectory::new((7, 2)).take(8).collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![
+                (7, 2),
+                (13, 3),
+                (18, 3),
+                (22, 2),
+                (25, 0),
+                (27, -3),
+                (28, -7),
+                (28, -12)
+            ],
+            positions
+        );
+    }
+
+    #[test]
+    fn test_launch() {
+        let target = input_generator(EXAMPLE).unwrap();
+
+        assert_eq!(Some(3), target.launch((7, 2)));
+        assert_eq!(Some(45), target.launch((6, 9)));
+        assert_eq!(None, target.launch((17, -4)));
+    }
+
+    #[test]
+    fn test_velocity_bounds() {
+        let target = input_generator(EXAMPLE).unwrap();
+
+        assert_eq!((6..=30, -10..=9), target.velocity_bounds());
+    }
+
+    #[test]
+    fn test_target_above_and_left() {
+        // Above and behind the launcher the probe has to be fired up and backwards
+        let target = input_generator("target area: x=-30..-20, y=5..10").unwrap();
+        let hits = target.hits().collect::<Vec<_>>();
+
+        assert_eq!(Some(10), target.launch((-6, 4)));
+        assert!(!hits.is_empty());
+        assert!(hits.iter().all(|&((vx, vy), _)| vx < 0 && vy > 0));
+    }
+
+    #[test]
+    fn test_invalid_target() {
+        assert_eq!(
+            "line 1, column 24: expected a range from the lowest to the highest, found `y=-5..-10`",
+            input_generator("target area: x=20..30, y=-5..-10")
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(
+            "line 1, column 26: expected a number, found `-9223372036854775808`",
+            input_generator("target area: x=20..30, y=-9223372036854775808..-5")
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_largest_target() {
+        let area = input_generator(&format!(
+            "target area: x={min}..{max}, y={min}..{max}",
+            min = i32::MIN,
+            max = i32::MAX
+        ))
+        .unwrap();
+        let (vx, vy) = area.velocity_bounds();
+
+        // The fastest probes either way are followed without overflowing, the one going up
+        // overshoots the area before it comes back down
+        assert_eq!(Some(0), area.launch((*vx.start(), *vy.start())));
+        assert_eq!(None, area.launch((*vx.end(), *vy.end())));
+    }
+
+    proptest! {
+        #[test]
+        fn test_target_area_round_trip(area in target_area()) {
+            let input = format!(
+                "target area: x={}..{}, y={}..{}",
+                area.x.start(),
+                area.x.end(),
+                area.y.start(),
+                area.y.end()
+            );
+
+            prop_assert_eq!(Ok(area), input_generator(&input));
+        }
+    }
+}
@@ -0,0 +1,458 @@
+use std::fmt::{self, Display, Formatter};
+use std::ops::Add;
+use std::str::FromStr;
+
+use crate::error::{parse_number, ParseError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Node {
+    Regular(u32),
+    /// A pair of the nodes at the given indices in the arena.
+    Pair(usize, usize),
+}
+
+/// A snailfish number, stored as an arena of nodes with the pairs referring to their elements by
+/// index. Adding two numbers reduces the result by exploding and splitting pairs.
+#[derive(Debug, Clone)]
+pub struct SnailfishNumber {
+    nodes: Vec<Node>,
+    root: usize,
+}
+
+impl SnailfishNumber {
+    fn copy_node(&self, node: usize, nodes: &mut Vec<Node>) -> usize {
+        let copy = match self.nodes[node] {
+            Node::Regular(value) => Node::Regular(value),
+            Node::Pair(left, right) => {
+                Node::Pair(self.copy_node(left, nodes), self.copy_node(right, nodes))
+            }
+        };
+
+        nodes.push(copy);
+        nodes.len() - 1
+    }
+
+    fn regular(&self, node: usize) -> Option<u32> {
+        match self.nodes[node] {
+            Node::Regular(value) => Some(value),
+            Node::Pair(..) => None,
+        }
+    }
+
+    /// Collect the regular numbers from left to right, and find the leftmost pair of two regular
+    /// numbers nested inside four pairs, with the position of its left number among the regulars.
+    fn visit(
+        &self,
+        node: usize,
+        depth: usize,
+        regulars: &mut Vec<usize>,
+        exploding: &mut Option<(usize, usize)>,
+    ) {
+        match self.nodes[node] {
+            Node::Regular(_) => regulars.push(node),
+            Node::Pair(left, right) => {
+                let is_simple = self.regular(left).is_some() && self.regular(right).is_some();
+
+                if depth >= 4 && is_simple && exploding.is_none() {
+                    *exploding = Some((node, regulars.len()));
+                }
+
+                self.visit(left, depth + 1, regulars, exploding);
+                self.visit(right, depth + 1, regulars, exploding);
+            }
+        }
+    }
+
+    fn add_to_regular(&mut self, node: usize, amount: u32) {
+        if let Node::Regular(value) = &mut self.nodes[node] {
+            *value += amount;
+        }
+    }
+
+    /// Explode the leftmost pair nested inside four pairs, returns whether there was one.
+    fn explode(&mut self) -> bool {
+        let mut regulars = vec![];
+        let mut exploding = None;
+        self.visit(self.root, 0, &mut regulars, &mut exploding);
+
+        let (pair, position) = match exploding {
+            Some(exploding) => exploding,
+            None => return false,
+        };
+
+        let (left, right) = match self.nodes[pair] {
+            Node::Pair(left, right) => (left, right),
+            Node::Regular(_) => unreachable!("only pairs explode"),
+        };
+        let left_value = self.regular(left).unwrap_or_default();
+        let right_value = self.regular(right).unwrap_or_default();
+
+        if let Some(&previous) = position.checked_sub(1).and_then(|i| regulars.get(i)) {
+            self.add_to_regular(previous, left_value);
+        }
+
+        if let Some(&next) = regulars.get(position + 2) {
+            self.add_to_regular(next, right_value);
+        }
+
+        // The elements of the pair are left in the arena, they are dropped by the next addition
+        self.nodes[pair] = Node::Regular(0);
+
+        true
+    }
+
+    fn find_split(&self, node: usize) -> Option<usize> {
+        match self.nodes[node] {
+            Node::Regular(value) if value >= 10 => Some(node),
+            Node::Regular(_) => None,
+            Node::Pair(left, right) => self.find_split(left).or_else(|| self.find_split(right)),
+        }
+    }
+
+    /// Split the leftmost regular number of 10 or more, returns whether there was one.
+    fn split(&mut self) -> bool {
+        let node = match self.find_split(self.root) {
+            Some(node) => node,
+            None => return false,
+        };
+
+        let value = self.regular(node).unwrap_or_default();
+
+        self.nodes.push(Node::Regular(value / 2));
+        self.nodes.push(Node::Regular(value.div_ceil(2)));
+        self.nodes[node] = Node::Pair(self.nodes.len() - 2, self.nodes.len() - 1);
+
+        true
+    }
+
+    /// Explode and split until neither applies, explosions always go first.
+    pub fn reduce(&mut self) {
+        while self.explode() || self.split() {}
+    }
+
+    fn node_magnitude(&self, node: usize) -> u64 {
+        match self.nodes[node] {
+            Node::Regular(value) => value as u64,
+            Node::Pair(left, right) => {
+                3 * self.node_magnitude(left) + 2 * self.node_magnitude(right)
+            }
+        }
+    }
+
+    pub fn magnitude(&self) -> u64 {
+        self.node_magnitude(self.root)
+    }
+
+    fn node_eq(&self, node: usize, other: &Self, other_node: usize) -> bool {
+        match (self.nodes[node], other.nodes[other_node]) {
+            (Node::Regular(a), Node::Regular(b)) => a == b,
+            (Node::Pair(a_left, a_right), Node::Pair(b_left, b_right)) => {
+                self.node_eq(a_left, other, b_left) && self.node_eq(a_right, other, b_right)
+            }
+            _ => false,
+        }
+    }
+
+    fn write_node(&self, f: &mut Formatter<'_>, node: usize) -> fmt::Result {
+        match self.nodes[node] {
+            Node::Regular(value) => write!(f, "{}", value),
+            Node::Pair(left, right) => {
+                f.write_str("[")?;
+                self.write_node(f, left)?;
+                f.write_str(",")?;
+                self.write_node(f, right)?;
+                f.write_str("]")
+            }
+        }
+    }
+}
+
+/// Numbers are equal when they have the same shape and values, wherever their nodes are stored.
+impl PartialEq for SnailfishNumber {
+    fn eq(&self, other: &Self) -> bool {
+        self.node_eq(self.root, other, other.root)
+    }
+}
+
+impl Eq for SnailfishNumber {}
+
+impl Add for &SnailfishNumber {
+    type Output = SnailfishNumber;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        // Copying only the reachable nodes drops whatever earlier explosions left behind
+        let mut nodes = Vec::with_capacity(self.nodes.len() + rhs.nodes.len() + 1);
+        let left = self.copy_node(self.root, &mut nodes);
+        let right = rhs.copy_node(rhs.root, &mut nodes);
+        nodes.push(Node::Pair(left, right));
+
+        let mut sum = SnailfishNumber {
+            root: nodes.len() - 1,
+            nodes,
+        };
+        sum.reduce();
+        sum
+    }
+}
+
+impl Add for SnailfishNumber {
+    type Output = SnailfishNumber;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+impl Display for SnailfishNumber {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.write_node(f, self.root)
+    }
+}
+
+/// How deep pairs may be nested in each other, so the number can be worked on without running out
+/// of stack.
+const MAX_NESTING: usize = 100;
+
+struct Parser<'a> {
+    source: &'a str,
+    position: usize,
+    nodes: Vec<Node>,
+}
+
+impl Parser<'_> {
+    fn next_token(&self) -> &str {
+        let rest = &self.source[self.position..];
+        let length = rest.chars().next().map_or(0, char::len_utf8);
+
+        &rest[..length]
+    }
+
+    fn expect(&mut self, token: &str, expected: &'static str) -> Result<(), ParseError> {
+        match self.next_token() {
+            "" => Err(ParseError::unexpected_end(self.source, expected)),
+            next if next == token => {
+                self.position += token.len();
+                Ok(())
+            }
+            next => Err(ParseError::unexpected(self.source, next, expected)),
+        }
+    }
+
+    fn parse_element(&mut self, depth: usize) -> Result<usize, ParseError> {
+        let rest = &self.source[self.position..];
+        let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+
+        let node = if digits > 0 {
+            self.position += digits;
+            Node::Regular(parse_number(self.source, &rest[..digits])?)
+        } else {
+            if depth >= MAX_NESTING && self.next_token() == "[" {
+                return Err(ParseError::unexpected(
+                    self.source,
+                    self.next_token(),
+                    "pairs nested at most 100 deep",
+                ));
+            }
+
+            self.expect("[", "`[` or a number")?;
+            let left = self.parse_element(depth + 1)?;
+            self.expect(",", "`,`")?;
+            let right = self.parse_element(depth + 1)?;
+            self.expect("]", "`]`")?;
+            Node::Pair(left, right)
+        };
+
+        self.nodes.push(node);
+        Ok(self.nodes.len() - 1)
+    }
+}
+
+impl FromStr for SnailfishNumber {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            source: s,
+            position: 0,
+            nodes: vec![],
+        };
+        let root = parser.parse_element(0)?;
+
+        if parser.position < s.len() {
+            return Err(ParseError::unexpected(
+                s,
+                &s[parser.position..],
+                "the end of the number",
+            ));
+        }
+
+        Ok(Self {
+            nodes: parser.nodes,
+            root,
+        })
+    }
+}
+
+#[aoc_generator(day18)]
+pub fn input_generator(input: &str) -> Result<Vec<SnailfishNumber>, ParseError> {
+    if input.is_empty() {
+        return Err(ParseError::unexpected_end(input, "a snailfish number"));
+    }
+
+    input
+        .lines()
+        .map(|line| {
+            let number = line
+                .parse()
+                .map_err(|e: ParseError| e.within(input, line))?;
+
+            // The numbers in the homework are reduced, larger regular numbers could overflow while
+            // they are added
+            if let Some(start) = line
+                .as_bytes()
+                .windows(2)
+                .position(|pair| pair.iter().all(u8::is_ascii_digit))
+            {
+                let digits = line[start..]
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(line.len() - start);
+
+                return Err(ParseError::unexpected(
+                    input,
+                    &line[start..start + digits],
+                    "a regular number from 0 to 9",
+                ));
+            }
+
+            Ok(number)
+        })
+        .collect()
+}
+
+#[aoc(day18, part1)]
+pub fn solve_part1(input: &[SnailfishNumber]) -> u64 {
+    input
+        .iter()
+        .skip(1)
+        .fold(input[0].clone(), |sum, number| &sum + number)
+        .magnitude()
+}
+
+#[aoc(day18, part2)]
+pub fn solve_part2(input: &[SnailfishNumber]) -> u64 {
+    // Addition is not commutative, so both orders of every pair are tried
+    (0..input.len())
+        .flat_map(|a| (0..input.len()).map(move |b| (a, b)))
+        .filter(|(a, b)| a != b)
+        .map(|(a, b)| (&input[a] + &input[b]).magnitude())
+        .max()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// The text of a snailfish number of any depth, with regular numbers of any size.
+    fn text() -> impl Strategy<Value = String> {
+        let pair = |(left, right): (String, String)| format!("[{},{}]", left, right);
+        let element = any::<u32>()
+            .prop_map(|number| number.to_string())
+            .prop_recursive(8, 64, 2, move |element| {
+                (element.clone(), element).prop_map(pair)
+            });
+
+        (element.clone(), element).prop_map(pair)
+    }
+
+    fn number(s: &str) -> SnailfishNumber {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let input = "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]";
+
+        assert_eq!(input, number(input).to_string());
+        assert_eq!("[12,3]", number("[12,3]").to_string());
+    }
+
+    #[test]
+    fn test_explode() {
+        let cases = [
+            ("[[[[[9,8],1],2],3],4]", "[[[[0,9],2],3],4]"),
+            ("[7,[6,[5,[4,[3,2]]]]]", "[7,[6,[5,[7,0]]]]"),
+            ("[[6,[5,[4,[3,2]]]],1]", "[[6,[5,[7,0]]],3]"),
+            (
+                "[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]",
+                "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]",
+            ),
+        ];
+
+        for (input, expected) in cases {
+            let mut number = number(input);
+
+            assert!(number.explode());
+            assert_eq!(expected, number.to_string());
+        }
+    }
+
+    #[test]
+    fn test_add() {
+        let sum = number("[[[[4,3],4],4],[7,[[8,4],9]]]") + number("[1,1]");
+
+        assert_eq!(number("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]"), sum);
+    }
+
+    #[test]
+    fn test_magnitude() {
+        assert_eq!(143, number("[[1,2],[[3,4],5]]").magnitude());
+        assert_eq!(
+            3488,
+            number("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]").magnitude()
+        );
+    }
+
+    #[test]
+    fn test_invalid_number() {
+        assert_eq!(
+            "line 2, column 4: expected `,`, found `]`",
+            input_generator("[1,2]\n[[1]]").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "line 1, column 6: expected the end of the number, found `]`",
+            input_generator("[1,2]]").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "line 2, column 4: expected a regular number from 0 to 9, found `4294967295`",
+            input_generator("[1,2]\n[1,4294967295]")
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_nesting_limit() {
+        let nested = |depth: usize| format!("{}0{}", "[".repeat(depth), ",0]".repeat(depth));
+
+        assert!(nested(MAX_NESTING).parse::<SnailfishNumber>().is_ok());
+        assert_eq!(
+            "line 1, column 101: expected pairs nested at most 100 deep, found `[`",
+            nested(200_000)
+                .parse::<SnailfishNumber>()
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_text_round_trip(text in text()) {
+            let number = text.parse::<SnailfishNumber>();
+
+            prop_assert_eq!(Ok(text), number.map(|number| number.to_string()));
+        }
+    }
+}
@@ -0,0 +1,473 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+
+use crate::error::{parse_number, ParseError};
+
+/// The number of beacons two scanners need to have in common for them to be aligned.
+pub const MIN_OVERLAP: usize = 12;
+
+/// How far a scanner can see along every axis, which keeps the coordinates of every scanner and
+/// beacon far from overflowing.
+pub const SCANNER_RANGE: i32 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl Point {
+    pub fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    fn coordinates(&self) -> [i32; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    pub fn manhattan_distance(&self, other: &Point) -> i32 {
+        let difference = *self - *other;
+
+        difference.x.abs() + difference.y.abs() + difference.z.abs()
+    }
+
+    fn squared_distance(&self, other: &Point) -> i64 {
+        let difference = *self - *other;
+
+        difference
+            .coordinates()
+            .iter()
+            .map(|&coordinate| coordinate as i64 * coordinate as i64)
+            .sum()
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl FromStr for Point {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut coordinates = s.split(',');
+        let mut next = || {
+            coordinates
+                .next()
+                .ok_or_else(|| ParseError::unexpected(s, s, "a point like `x,y,z`"))
+                .and_then(|coordinate| {
+                    let value: i32 = parse_number(s, coordinate)?;
+
+                    if value.abs() > SCANNER_RANGE {
+                        return Err(ParseError::unexpected(
+                            s,
+                            coordinate,
+                            "a coordinate from -1000 to 1000",
+                        ));
+                    }
+
+                    Ok(value)
+                })
+        };
+
+        let point = Point::new(next()?, next()?, next()?);
+
+        if coordinates.next().is_some() {
+            return Err(ParseError::unexpected(s, s, "a point like `x,y,z`"));
+        }
+
+        Ok(point)
+    }
+}
+
+/// One of the 24 ways a scanner can be facing, as a rotation matrix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rotation([[i32; 3]; 3]);
+
+impl Rotation {
+    /// All 24 rotations, the identity first. Each one is a permutation of the axes with signs
+    /// chosen so that the matrix does not mirror, which is when its determinant is 1.
+    pub fn all() -> Vec<Rotation> {
+        let permutations = [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ];
+
+        let mut rotations = vec![];
+
+        for permutation in permutations.iter() {
+            for signs in 0..8 {
+                let mut matrix = [[0; 3]; 3];
+
+                for (row, &column) in permutation.iter().enumerate() {
+                    matrix[row][column] = if signs >> row & 1 == 0 { 1 } else { -1 };
+                }
+
+                let rotation = Rotation(matrix);
+                if rotation.determinant() == 1 {
+                    rotations.push(rotation);
+                }
+            }
+        }
+
+        rotations
+    }
+
+    fn determinant(&self) -> i32 {
+        let m = self.0;
+
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    pub fn apply(&self, point: Point) -> Point {
+        let coordinates = point.coordinates();
+        let [x, y, z] = self
+            .0
+            .map(|row| row.iter().zip(coordinates.iter()).map(|(a, b)| a * b).sum());
+
+        Point::new(x, y, z)
+    }
+}
+
+/// The beacons a single scanner detected, relative to its own position and orientation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scanner {
+    pub id: u32,
+    pub beacons: Vec<Point>,
+}
+
+impl Scanner {
+    /// The squared distances between every two beacons, sorted. They don't depend on where the
+    /// scanner is or how it is facing, so scanners sharing beacons share these distances.
+    fn fingerprint(&self) -> Vec<i64> {
+        let mut distances = vec![];
+
+        for (i, a) in self.beacons.iter().enumerate() {
+            for b in &self.beacons[i + 1..] {
+                distances.push(a.squared_distance(b));
+            }
+        }
+
+        distances.sort_unstable();
+        distances
+    }
+}
+
+/// The number of values two sorted lists have in common, counting repeated values.
+fn common_count(a: &[i64], b: &[i64]) -> usize {
+    let (mut i, mut j, mut count) = (0, 0, 0);
+
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            Ordering::Less => i += 1,
+            Ordering::Greater => j += 1,
+            Ordering::Equal => {
+                count += 1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+
+    count
+}
+
+/// Find the rotation and position of a scanner that makes at least [`MIN_OVERLAP`] of its beacons
+/// line up with the `known` beacons.
+pub fn align(known: &[Point], scanner: &Scanner) -> Option<(Rotation, Point)> {
+    for rotation in Rotation::all() {
+        let rotated = scanner
+            .beacons
+            .iter()
+            .map(|&beacon| rotation.apply(beacon))
+            .collect::<Vec<_>>();
+
+        let mut offsets = HashMap::new();
+
+        for &a in known {
+            for &b in &rotated {
+                let count = offsets.entry(a - b).or_insert(0);
+                *count += 1;
+
+                if *count >= MIN_OVERLAP {
+                    return Some((rotation, a - b));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// The positions of all scanners and beacons, relative to the first scanner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BeaconMap {
+    pub scanners: Vec<Point>,
+    pub beacons: BTreeSet<Point>,
+}
+
+impl BeaconMap {
+    /// Align every scanner with one that has already been placed, starting from the first one.
+    /// Fails with the index of the first scanner that could not be placed, when some scanners do
+    /// not overlap with any of the others.
+    pub fn reconstruct(scanners: &[Scanner]) -> Result<Self, usize> {
+        let fingerprints = scanners
+            .iter()
+            .map(Scanner::fingerprint)
+            .collect::<Vec<_>>();
+        // Shared beacons give every pair of them the same distance in both scanners
+        let min_common = MIN_OVERLAP * (MIN_OVERLAP - 1) / 2;
+
+        let mut positions = vec![None; scanners.len()];
+        let mut beacons = vec![vec![]; scanners.len()];
+        let mut queue = VecDeque::new();
+
+        if let Some(first) = scanners.first() {
+            positions[0] = Some(Point::new(0, 0, 0));
+            beacons[0] = first.beacons.clone();
+            queue.push_back(0);
+        }
+
+        while let Some(placed) = queue.pop_front() {
+            for (index, scanner) in scanners.iter().enumerate() {
+                if positions[index].is_some()
+                    || common_count(&fingerprints[placed], &fingerprints[index]) < min_common
+                {
+                    continue;
+                }
+
+                if let Some((rotation, position)) = align(&beacons[placed], scanner) {
+                    positions[index] = Some(position);
+                    beacons[index] = scanner
+                        .beacons
+                        .iter()
+                        .map(|&beacon| rotation.apply(beacon) + position)
+                        .collect();
+                    queue.push_back(index);
+                }
+            }
+        }
+
+        if let Some(unplaced) = positions.iter().position(Option::is_none) {
+            return Err(unplaced);
+        }
+
+        Ok(Self {
+            scanners: positions.into_iter().flatten().collect(),
+            beacons: beacons.into_iter().flatten().collect(),
+        })
+    }
+
+    /// The largest Manhattan distance between any two scanners.
+    pub fn largest_distance(&self) -> i32 {
+        self.scanners
+            .iter()
+            .flat_map(|a| self.scanners.iter().map(move |b| a.manhattan_distance(b)))
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+impl FromStr for Scanner {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+        let header = lines.next().unwrap_or_default();
+
+        let id = header
+            .strip_prefix("--- scanner ")
+            .and_then(|header| header.strip_suffix(" ---"))
+            .ok_or_else(|| {
+                ParseError::unexpected(s, header, "a header like `--- scanner 0 ---`")
+            })?;
+
+        Ok(Self {
+            id: parse_number(s, id)?,
+            beacons: lines
+                .map(|line| line.parse().map_err(|e: ParseError| e.within(s, line)))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// Parse the scanners and place them all, both parts use the same map so it is only built once.
+#[aoc_generator(day19)]
+pub fn input_generator(input: &str) -> Result<BeaconMap, ParseError> {
+    let blocks = input.split("\n\n").collect::<Vec<_>>();
+    let scanners = blocks
+        .iter()
+        .map(|block| {
+            block
+                .parse()
+                .map_err(|e: ParseError| e.within(input, block))
+        })
+        .collect::<Result<Vec<Scanner>, _>>()?;
+
+    BeaconMap::reconstruct(&scanners).map_err(|unplaced| {
+        let header = blocks[unplaced].lines().next().unwrap_or_default();
+
+        ParseError::unexpected(
+            input,
+            header,
+            "a scanner sharing 12 beacons with another scanner",
+        )
+    })
+}
+
+#[aoc(day19, part1)]
+pub fn solve_part1(input: &BeaconMap) -> usize {
+    input.beacons.len()
+}
+
+#[aoc(day19, part2)]
+pub fn solve_part2(input: &BeaconMap) -> i32 {
+    input.largest_distance()
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn scanner() -> impl Strategy<Value = Scanner> {
+        let coordinate = -SCANNER_RANGE..=SCANNER_RANGE;
+        let point = (coordinate.clone(), coordinate.clone(), coordinate)
+            .prop_map(|(x, y, z)| Point::new(x, y, z));
+
+        (any::<u32>(), prop::collection::vec(point, 0..30))
+            .prop_map(|(id, beacons)| Scanner { id, beacons })
+    }
+
+    fn render(scanner: &Scanner) -> String {
+        let beacons = scanner
+            .beacons
+            .iter()
+            .map(|beacon| format!("\n{},{},{}", beacon.x, beacon.y, beacon.z));
+
+        format!(
+            "--- scanner {} ---{}",
+            scanner.id,
+            beacons.collect::<String>()
+        )
+    }
+
+    #[test]
+    fn test_rotations() {
+        let rotations = Rotation::all();
+        let point = Point::new(1, 2, 3);
+
+        let mut images = rotations
+            .iter()
+            .map(|rotation| rotation.apply(point))
+            .collect::<Vec<_>>();
+        images.sort();
+        images.dedup();
+
+        assert_eq!(24, images.len());
+        assert_eq!(point, rotations[0].apply(point));
+    }
+
+    #[test]
+    fn test_align() {
+        let known = (0..MIN_OVERLAP as i32)
+            .map(|i| Point::new(i * 7, i * i, 100 - i * 3))
+            .collect::<Vec<_>>();
+
+        // The same beacons seen upside down from a scanner at (10, -20, 5)
+        let position = Point::new(10, -20, 5);
+        let scanner = Scanner {
+            id: 1,
+            beacons: known
+                .iter()
+                .map(|&beacon| {
+                    let relative = beacon - position;
+                    Point::new(relative.x, -relative.y, -relative.z)
+                })
+                .collect(),
+        };
+
+        let (rotation, found) = align(&known, &scanner).unwrap();
+
+        assert_eq!(position, found);
+        assert_eq!(known[3], rotation.apply(scanner.beacons[3]) + found);
+        assert_eq!(None, align(&known[1..], &scanner));
+    }
+
+    #[test]
+    fn test_invalid_scanner() {
+        assert_eq!(
+            "line 3, column 1: expected a point like `x,y,z`, found `1,2`",
+            input_generator("--- scanner 0 ---\n1,2,3\n1,2")
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_reconstruct() {
+        let map =
+            input_generator(include_str!("../examples/2021/day19/example.txt").trim_end()).unwrap();
+
+        assert_eq!(
+            vec![
+                Point::new(0, 0, 0),
+                Point::new(68, -1246, -43),
+                Point::new(1105, -1205, 1229),
+                Point::new(-92, -2380, -20),
+                Point::new(-20, -1133, 1061),
+            ],
+            map.scanners
+        );
+    }
+
+    #[test]
+    fn test_coordinate_out_of_range() {
+        assert_eq!(
+            "line 2, column 1: expected a coordinate from -1000 to 1000, found `2147483647`",
+            input_generator("--- scanner 0 ---\n2147483647,0,0\n-2147483648,0,0")
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_scanner_without_overlap() {
+        assert_eq!(
+            "line 4, column 1: expected a scanner sharing 12 beacons with another scanner, found \
+             `--- scanner 1 ---`",
+            input_generator("--- scanner 0 ---\n1,2,3\n\n--- scanner 1 ---\n4,5,6")
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_scanner_round_trip(scanner in scanner()) {
+            prop_assert_eq!(Ok(scanner.clone()), render(&scanner).parse::<Scanner>());
+        }
+    }
+}
@@ -1,5 +1,8 @@
+use std::fmt::{self, Display, Formatter};
 use std::str::FromStr;
 
+use crate::error::{parse_number, ParseError};
+
 pub struct Submarine {
     horizontal: isize,
     depth: isize,
@@ -22,6 +25,7 @@ impl Default for Submarine {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Direction {
     Forward,
     Up,
@@ -29,18 +33,31 @@ pub enum Direction {
 }
 
 impl FromStr for Direction {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "forward" => Ok(Direction::Forward),
             "up" => Ok(Direction::Up),
             "down" => Ok(Direction::Down),
-            _ => Err(()),
+            _ => Err(ParseError::unexpected(s, s, "forward, up or down")),
         }
     }
 }
 
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Direction::Forward => "forward",
+            Direction::Up => "up",
+            Direction::Down => "down",
+        };
+
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Instruction {
     direction: Direction,
     amount: isize,
@@ -57,23 +74,40 @@ impl Instruction {
 }
 
 impl FromStr for Instruction {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut split = s.split_whitespace();
 
+        let direction = split
+            .next()
+            .ok_or_else(|| ParseError::unexpected_end(s, "a direction"))?;
+        let amount = split
+            .next()
+            .ok_or_else(|| ParseError::unexpected_end(s, "an amount"))?;
+
+        if let Some(extra) = split.next() {
+            return Err(ParseError::unexpected(s, extra, "end of line"));
+        }
+
         Ok(Instruction {
-            direction: Direction::from_str(split.next().unwrap()).unwrap(),
-            amount: split.next().unwrap().parse::<isize>().unwrap(),
+            direction: Direction::from_str(direction).map_err(|e| e.within(s, direction))?,
+            amount: parse_number(s, amount)?,
         })
     }
 }
 
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.direction, self.amount)
+    }
+}
+
 #[aoc_generator(day2)]
-pub fn input_generator(input: &str) -> Vec<Instruction> {
+pub fn input_generator(input: &str) -> Result<Vec<Instruction>, ParseError> {
     input
         .lines()
-        .map(|line| Instruction::from_str(line).unwrap())
+        .map(|line| Instruction::from_str(line).map_err(|e| e.within(input, line)))
         .collect()
 }
 
@@ -109,3 +143,56 @@ pub fn solve_part2(input: &[Instruction]) -> isize {
 
     submarine.horizontal * submarine.depth
 }
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn instruction() -> impl Strategy<Value = Instruction> {
+        let direction = prop_oneof![
+            Just(Direction::Forward),
+            Just(Direction::Up),
+            Just(Direction::Down)
+        ];
+
+        (direction, any::<isize>())
+            .prop_map(|(direction, amount)| Instruction { direction, amount })
+    }
+
+    #[test]
+    fn test_part1() {
+        let input =
+            input_generator("forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2").unwrap();
+
+        assert_eq!(150, solve_part1(&input))
+    }
+
+    #[test]
+    fn test_invalid_direction() {
+        let error = input_generator("forward 5\nleft 3").err().unwrap();
+
+        assert_eq!(
+            "line 2, column 1: expected forward, up or down, found `left`",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn test_missing_amount() {
+        let error = input_generator("forward 5\nup").err().unwrap();
+
+        assert_eq!(
+            "line 2, column 3: expected an amount, found end of input",
+            error.to_string()
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_instruction_round_trip(instruction in instruction()) {
+            prop_assert_eq!(Ok(instruction.clone()), instruction.to_string().parse());
+        }
+    }
+}
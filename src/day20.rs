@@ -0,0 +1,275 @@
+use std::fmt::{self, Display, Formatter};
+use std::iter;
+
+use crate::error::ParseError;
+use crate::grid::Grid;
+
+/// The number of entries in an image enhancement algorithm, one for every 3x3 square of pixels.
+pub const ALGORITHM_SIZE: usize = 512;
+
+fn parse_pixels(source: &str, line: &str) -> Result<Vec<bool>, ParseError> {
+    line.char_indices()
+        .map(|(index, c)| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(ParseError::unexpected(
+                source,
+                &line[index..index + c.len_utf8()],
+                "`#` or `.`",
+            )),
+        })
+        .collect()
+}
+
+/// A finite image on an infinite background where every pixel has the same colour.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pixels: Grid<bool>,
+    background: bool,
+}
+
+impl Image {
+    pub fn new(pixels: Grid<bool>) -> Self {
+        Self {
+            pixels,
+            background: false,
+        }
+    }
+
+    /// Whether the pixel is lit, with the top left pixel of the finite image at (0, 0).
+    pub fn is_lit(&self, x: isize, y: isize) -> bool {
+        if x < 0 || y < 0 {
+            return self.background;
+        }
+
+        self.pixels
+            .get((x as usize, y as usize))
+            .copied()
+            .unwrap_or(self.background)
+    }
+
+    pub fn background(&self) -> bool {
+        self.background
+    }
+
+    /// Apply the algorithm to every pixel, the image grows by one pixel on every side since those
+    /// are the only ones next to the finite image. The background is enhanced as a whole, all of it
+    /// lights up when the first entry is lit and goes out again when the last entry isn't.
+    pub fn enhance(&self, algorithm: &[bool]) -> Image {
+        let width = self.pixels.width() + 2;
+        let height = self.pixels.height() + 2;
+
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x as isize - 1, y as isize - 1)))
+            .map(|(x, y)| {
+                let index = (-1..=1)
+                    .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+                    .fold(0, |index, (dx, dy)| {
+                        index << 1 | self.is_lit(x + dx, y + dy) as usize
+                    });
+
+                algorithm[index]
+            })
+            .collect();
+
+        let background = algorithm[if self.background {
+            ALGORITHM_SIZE - 1
+        } else {
+            0
+        }];
+
+        Image {
+            pixels: Grid::new(width, height, cells),
+            background,
+        }
+    }
+
+    /// The number of lit pixels, or `None` when the infinite background is lit.
+    pub fn lit_count(&self) -> Option<usize> {
+        if self.background {
+            return None;
+        }
+
+        Some(self.pixels.values().filter(|&&lit| lit).count())
+    }
+}
+
+/// Render the finite part of the image with `#` for lit pixels and `.` for dark ones.
+impl Display for Image {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.pixels.map(|&lit| if lit { '#' } else { '.' }))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input {
+    pub algorithm: Vec<bool>,
+    pub image: Image,
+}
+
+impl Input {
+    /// The original image followed by the image after every enhancement step, for inspecting
+    /// the intermediate images.
+    pub fn steps(&self) -> impl Iterator<Item = Image> + '_ {
+        iter::successors(Some(self.image.clone()), move |image| {
+            Some(image.enhance(&self.algorithm))
+        })
+    }
+
+    pub fn enhanced(&self, steps: usize) -> Image {
+        self.steps().nth(steps).expect("the steps never end")
+    }
+}
+
+#[aoc_generator(day20)]
+pub fn input_generator(input: &str) -> Result<Input, ParseError> {
+    let (algorithm, image) = input
+        .split_once("\n\n")
+        .ok_or_else(|| ParseError::unexpected_end(input, "an algorithm and an image"))?;
+
+    let algorithm_line = algorithm;
+    let algorithm = parse_pixels(input, algorithm_line)?;
+
+    if algorithm.len() != ALGORITHM_SIZE {
+        return Err(ParseError::unexpected(
+            input,
+            algorithm_line,
+            "an enhancement algorithm of 512 pixels",
+        ));
+    }
+
+    // Lighting the dark background while keeping a lit one lit leaves infinitely many pixels lit
+    if algorithm[0] && algorithm[ALGORITHM_SIZE - 1] {
+        return Err(ParseError::unexpected(
+            input,
+            &algorithm_line[ALGORITHM_SIZE - 1..],
+            "an algorithm that darkens a lit background when it lights a dark one",
+        ));
+    }
+
+    let rows = image.lines().collect::<Vec<_>>();
+    let width = rows.first().map_or(0, |row| row.len());
+    let mut cells = Vec::with_capacity(width * rows.len());
+
+    for row in &rows {
+        if row.len() != width {
+            return Err(ParseError::unexpected(input, row, "rows of equal width"));
+        }
+
+        cells.extend(parse_pixels(input, row)?);
+    }
+
+    Ok(Input {
+        algorithm,
+        image: Image::new(Grid::new(width, rows.len(), cells)),
+    })
+}
+
+#[aoc(day20, part1)]
+pub fn solve_part1(input: &Input) -> usize {
+    input
+        .enhanced(2)
+        .lit_count()
+        .expect("the background is dark after an even number of steps")
+}
+
+#[aoc(day20, part2)]
+pub fn solve_part2(input: &Input) -> usize {
+    input
+        .enhanced(50)
+        .lit_count()
+        .expect("the background is dark after an even number of steps")
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn input() -> impl Strategy<Value = Input> {
+        let algorithm =
+            prop::collection::vec(any::<bool>(), ALGORITHM_SIZE).prop_map(|mut algorithm| {
+                // A lit background has to go dark again
+                algorithm[ALGORITHM_SIZE - 1] &= !algorithm[0];
+                algorithm
+            });
+        let image = (1..10usize, 1..10usize).prop_flat_map(|(width, height)| {
+            prop::collection::vec(any::<bool>(), width * height)
+                .prop_map(move |cells| Image::new(Grid::new(width, height, cells)))
+        });
+
+        (algorithm, image).prop_map(|(algorithm, image)| Input { algorithm, image })
+    }
+
+    fn render(input: &Input) -> String {
+        let algorithm = input
+            .algorithm
+            .iter()
+            .map(|&lit| if lit { '#' } else { '.' })
+            .collect::<String>();
+
+        format!("{}\n\n{}", algorithm, input.image)
+    }
+
+    fn flashing_input() -> Input {
+        // Lights every pixel surrounded by dark ones, and darkens every pixel surrounded by lit ones
+        let mut algorithm = vec![false; ALGORITHM_SIZE];
+        algorithm[0] = true;
+
+        Input {
+            algorithm,
+            image: Image::new(Grid::new(1, 1, vec![true])),
+        }
+    }
+
+    #[test]
+    fn test_enhance() {
+        let input =
+            input_generator(include_str!("../examples/2021/day20/example.txt").trim_end()).unwrap();
+        let image = input.enhanced(1);
+
+        assert_eq!(
+            ".##.##.\n#..#.#.\n##.#..#\n####..#\n.#..##.\n..##..#\n...#.#.\n",
+            image.to_string()
+        );
+        assert_eq!(Some(24), image.lit_count());
+    }
+
+    #[test]
+    fn test_infinite_background() {
+        let input = flashing_input();
+        let images = input.steps().take(3).collect::<Vec<_>>();
+
+        assert!(images[1].background());
+        assert_eq!(None, images[1].lit_count());
+        assert!(!images[2].background());
+        assert_eq!(Some(1), images[2].lit_count());
+    }
+
+    #[test]
+    fn test_invalid_algorithm() {
+        assert_eq!(
+            "line 1, column 1: expected an enhancement algorithm of 512 pixels, found `#.#`",
+            input_generator("#.#\n\n#").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_background_stays_lit() {
+        let input = format!("#{}#\n\n#", ".".repeat(ALGORITHM_SIZE - 2));
+
+        assert_eq!(
+            "line 1, column 512: expected an algorithm that darkens a lit background when it lights \
+             a dark one, found `#`",
+            input_generator(&input).unwrap_err().to_string()
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_input_round_trip(input in input()) {
+            prop_assert_eq!(Ok(input.clone()), input_generator(&render(&input)));
+        }
+    }
+}
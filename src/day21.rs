@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+use crate::error::{parse_number, ParseError};
+
+/// The starting positions of both players.
+pub type Start = [u32; 2];
+
+/// The shape of a game, so variants with other boards and goals can be played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    /// The number of spaces on the circular board, numbered from 1.
+    pub board_size: u32,
+    pub winning_score: u32,
+}
+
+impl Rules {
+    pub const DETERMINISTIC: Rules = Rules {
+        board_size: 10,
+        winning_score: 1000,
+    };
+
+    pub const DIRAC: Rules = Rules {
+        board_size: 10,
+        winning_score: 21,
+    };
+
+    fn advance(&self, position: u32, roll: u32) -> u32 {
+        (position - 1 + roll) % self.board_size + 1
+    }
+}
+
+/// Play with the deterministic die that rolls 1 to 100 in order, returns the score of the losing
+/// player times the number of times the die was rolled.
+pub fn play_deterministic(start: Start, rules: &Rules) -> u64 {
+    let mut positions = start;
+    let mut scores = [0, 0];
+    let mut rolls = 0;
+    let mut player = 0;
+
+    loop {
+        let roll = (0..3).map(|i| (rolls + i) % 100 + 1).sum::<u32>();
+        rolls += 3;
+
+        positions[player] = rules.advance(positions[player], roll);
+        scores[player] += positions[player];
+
+        if scores[player] >= rules.winning_score {
+            return scores[1 - player] as u64 * rolls as u64;
+        }
+
+        player = 1 - player;
+    }
+}
+
+/// The totals of three rolls of the three sided Dirac die, with how many universes roll them.
+const DIRAC_ROLLS: [(u32, u64); 7] = [(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)];
+
+type State = (Start, [u32; 2], usize);
+
+fn count_wins_from(state: State, rules: &Rules, cache: &mut HashMap<State, [u64; 2]>) -> [u64; 2] {
+    if let Some(&wins) = cache.get(&state) {
+        return wins;
+    }
+
+    let (positions, scores, player) = state;
+    let mut wins = [0, 0];
+
+    for &(roll, universes) in DIRAC_ROLLS.iter() {
+        let mut positions = positions;
+        let mut scores = scores;
+
+        positions[player] = rules.advance(positions[player], roll);
+        scores[player] += positions[player];
+
+        if scores[player] >= rules.winning_score {
+            wins[player] += universes;
+        } else {
+            let next = count_wins_from((positions, scores, 1 - player), rules, cache);
+            wins[0] += next[0] * universes;
+            wins[1] += next[1] * universes;
+        }
+    }
+
+    cache.insert(state, wins);
+    wins
+}
+
+/// Play with the Dirac die, returns the number of universes each player wins in. Universes that
+/// reach the same positions and scores on the same turn are only counted once.
+pub fn count_wins(start: Start, rules: &Rules) -> [u64; 2] {
+    count_wins_from((start, [0, 0], 0), rules, &mut HashMap::new())
+}
+
+#[aoc_generator(day21)]
+pub fn input_generator(input: &str) -> Result<Start, ParseError> {
+    let mut lines = input.lines();
+    let mut start = [0; 2];
+
+    for (player, position) in start.iter_mut().enumerate() {
+        let line = lines
+            .next()
+            .ok_or_else(|| ParseError::unexpected_end(input, "a starting position"))?;
+        let (_, value) = line
+            .split_once(" starting position: ")
+            .filter(|(name, _)| *name == format!("Player {}", player + 1))
+            .ok_or_else(|| {
+                ParseError::unexpected(input, line, "a line like `Player 1 starting position: 4`")
+            })?;
+
+        *position = parse_number(input, value)?;
+
+        if !(1..=Rules::DIRAC.board_size).contains(position) {
+            return Err(ParseError::unexpected(
+                input,
+                value,
+                "a position from 1 to 10",
+            ));
+        }
+    }
+
+    Ok(start)
+}
+
+#[aoc(day21, part1)]
+pub fn solve_part1(input: &Start) -> u64 {
+    play_deterministic(*input, &Rules::DETERMINISTIC)
+}
+
+#[aoc(day21, part2)]
+pub fn solve_part2(input: &Start) -> u64 {
+    let wins = count_wins(*input, &Rules::DIRAC);
+
+    wins[0].max(wins[1])
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_count_wins() {
+        assert_eq!(
+            [444356092776315, 341960390180808],
+            count_wins([4, 8], &Rules::DIRAC)
+        );
+    }
+
+    #[test]
+    fn test_variant_rules() {
+        // The first player reaches 1 on their first turn in every universe
+        let rules = Rules {
+            board_size: 10,
+            winning_score: 1,
+        };
+
+        assert_eq!([27, 0], count_wins([4, 8], &rules));
+
+        // On three spaces the deterministic die's totals of 6, 15 and 24 always return to space 3
+        let rules = Rules {
+            board_size: 3,
+            winning_score: 4,
+        };
+
+        assert_eq!(27, play_deterministic([3, 3], &rules));
+    }
+
+    #[test]
+    fn test_invalid_position() {
+        assert_eq!(
+            "line 2, column 29: expected a position from 1 to 10, found `11`",
+            input_generator("Player 1 starting position: 4\nPlayer 2 starting position: 11")
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_start_round_trip(start in [1..=10u32, 1..=10u32]) {
+            let input = format!(
+                "Player 1 starting position: {}\nPlayer 2 starting position: {}",
+                start[0], start[1]
+            );
+
+            prop_assert_eq!(Ok(start), input_generator(&input));
+        }
+    }
+}
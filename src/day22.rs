@@ -0,0 +1,274 @@
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use crate::error::{parse_number, ParseError};
+
+/// A box of cubes, with inclusive bounds on every axis. Parsed bounds fit in an `i32`, so volumes
+/// and sums of them fit in an `i128`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cuboid {
+    pub min: [i64; 3],
+    pub max: [i64; 3],
+}
+
+impl Cuboid {
+    pub fn new(min: [i64; 3], max: [i64; 3]) -> Self {
+        Self { min, max }
+    }
+
+    /// The number of cubes inside the cuboid.
+    pub fn volume(&self) -> u128 {
+        (0..3)
+            .map(|axis| (self.max[axis] - self.min[axis] + 1) as u128)
+            .product()
+    }
+
+    /// The cubes that are inside both cuboids, or `None` when they don't overlap.
+    pub fn intersection(&self, other: &Cuboid) -> Option<Cuboid> {
+        let mut min = [0; 3];
+        let mut max = [0; 3];
+
+        for axis in 0..3 {
+            min[axis] = self.min[axis].max(other.min[axis]);
+            max[axis] = self.max[axis].min(other.max[axis]);
+
+            if min[axis] > max[axis] {
+                return None;
+            }
+        }
+
+        Some(Cuboid::new(min, max))
+    }
+}
+
+/// A reboot step that turns every cube in the cuboid on or off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub on: bool,
+    pub cuboid: Cuboid,
+}
+
+/// The cubes the initialisation procedure is limited to.
+pub const INITIALISATION_REGION: Cuboid = Cuboid {
+    min: [-50; 3],
+    max: [50; 3],
+};
+
+/// Count the cubes that are on after the steps, starting with every cube off.
+///
+/// The lit cubes are kept as cuboids that are added or subtracted. Every step subtracts its
+/// overlap with each of them, so nothing is counted twice, and then adds itself when it is on.
+pub fn count_lit<'a>(steps: impl IntoIterator<Item = &'a Step>) -> u128 {
+    let mut cuboids = HashMap::<Cuboid, i64>::new();
+
+    for step in steps {
+        let mut changes = HashMap::new();
+
+        for (cuboid, &sign) in &cuboids {
+            if let Some(overlap) = step.cuboid.intersection(cuboid) {
+                *changes.entry(overlap).or_insert(0) -= sign;
+            }
+        }
+
+        if step.on {
+            *changes.entry(step.cuboid).or_insert(0) += 1;
+        }
+
+        for (cuboid, change) in changes {
+            *cuboids.entry(cuboid).or_insert(0) += change;
+        }
+
+        cuboids.retain(|_, sign| *sign != 0);
+    }
+
+    let total = cuboids
+        .iter()
+        .map(|(cuboid, &sign)| cuboid.volume() as i128 * sign as i128)
+        .sum::<i128>();
+
+    total as u128
+}
+
+fn parse_range(source: &str, token: &str, axis: &str) -> Result<(i64, i64), ParseError> {
+    let (min, max) = token
+        .strip_prefix(axis)
+        .and_then(|range| range.strip_prefix('='))
+        .and_then(|range| range.split_once(".."))
+        .ok_or_else(|| ParseError::unexpected(source, token, "a range like `x=10..12`"))?;
+
+    let range = (
+        parse_number::<i32>(source, min)? as i64,
+        parse_number::<i32>(source, max)? as i64,
+    );
+
+    if range.0 > range.1 {
+        return Err(ParseError::unexpected(
+            source,
+            token,
+            "a range from the lowest to the highest",
+        ));
+    }
+
+    Ok(range)
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (min, max) = (self.cuboid.min, self.cuboid.max);
+
+        write!(
+            f,
+            "{} x={}..{},y={}..{},z={}..{}",
+            if self.on { "on" } else { "off" },
+            min[0],
+            max[0],
+            min[1],
+            max[1],
+            min[2],
+            max[2]
+        )
+    }
+}
+
+impl FromStr for Step {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (state, ranges) = s.split_once(' ').ok_or_else(|| {
+            ParseError::unexpected(s, s, "a step like `on x=10..12,y=10..12,z=10..12`")
+        })?;
+
+        let on = match state {
+            "on" => true,
+            "off" => false,
+            _ => return Err(ParseError::unexpected(s, state, "`on` or `off`")),
+        };
+
+        let ranges = ranges.split(',').collect::<Vec<_>>();
+        if ranges.len() != 3 {
+            return Err(ParseError::unexpected(s, s, "ranges for x, y and z"));
+        }
+
+        let mut min = [0; 3];
+        let mut max = [0; 3];
+
+        for (axis, (name, range)) in ["x", "y", "z"].iter().zip(ranges).enumerate() {
+            let (low, high) = parse_range(s, range, name)?;
+            min[axis] = low;
+            max[axis] = high;
+        }
+
+        Ok(Self {
+            on,
+            cuboid: Cuboid::new(min, max),
+        })
+    }
+}
+
+#[aoc_generator(day22)]
+pub fn input_generator(input: &str) -> Result<Vec<Step>, ParseError> {
+    input
+        .lines()
+        .map(|line| line.parse().map_err(|e: ParseError| e.within(input, line)))
+        .collect()
+}
+
+#[aoc(day22, part1)]
+pub fn solve_part1(input: &[Step]) -> u128 {
+    let steps = input
+        .iter()
+        .filter_map(|step| {
+            step.cuboid
+                .intersection(&INITIALISATION_REGION)
+                .map(|cuboid| Step { cuboid, ..*step })
+        })
+        .collect::<Vec<_>>();
+
+    count_lit(&steps)
+}
+
+#[aoc(day22, part2)]
+pub fn solve_part2(input: &[Step]) -> u128 {
+    count_lit(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn step() -> impl Strategy<Value = Step> {
+        let range =
+            (any::<i32>(), any::<i32>()).prop_map(|(a, b)| (a.min(b) as i64, a.max(b) as i64));
+
+        (any::<bool>(), [range.clone(), range.clone(), range]).prop_map(|(on, ranges)| Step {
+            on,
+            cuboid: Cuboid::new(ranges.map(|range| range.0), ranges.map(|range| range.1)),
+        })
+    }
+
+    #[test]
+    fn test_cuboid() {
+        let a = Cuboid::new([10, 10, 10], [12, 12, 12]);
+        let b = Cuboid::new([11, 11, 11], [13, 13, 13]);
+
+        assert_eq!(27, a.volume());
+        assert_eq!(
+            Some(Cuboid::new([11, 11, 11], [12, 12, 12])),
+            a.intersection(&b)
+        );
+        assert_eq!(
+            None,
+            a.intersection(&Cuboid::new([13, 10, 10], [14, 12, 12]))
+        );
+    }
+
+    #[test]
+    fn test_count_lit() {
+        let steps =
+            input_generator(include_str!("../examples/2021/day22/small.txt").trim_end()).unwrap();
+
+        assert_eq!(
+            vec![27, 46, 38, 39],
+            (1..=4).map(|n| count_lit(&steps[..n])).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_largest_cuboid() {
+        let steps = input_generator(&format!(
+            "on x={min}..{max},y={min}..{max},z={min}..{max}",
+            min = i32::MIN,
+            max = i32::MAX
+        ))
+        .unwrap();
+
+        assert_eq!(1 << 96, solve_part2(&steps));
+        assert!(input_generator("on x=0..2147483648,y=0..1,z=0..1").is_err());
+    }
+
+    #[test]
+    fn test_invalid_step() {
+        assert_eq!(
+            "line 1, column 13: expected a range like `x=10..12`, found `z=10..12`",
+            input_generator("on x=10..12,z=10..12,y=10..12")
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_steps_round_trip(steps in prop::collection::vec(step(), 1..20)) {
+            let input = steps
+                .iter()
+                .map(|step| step.to_string())
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            prop_assert_eq!(Ok(steps), input_generator(&input));
+        }
+    }
+}
@@ -0,0 +1,471 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::error::ParseError;
+use crate::path::dijkstra;
+
+const HALLWAY_LENGTH: usize = 11;
+
+/// The hallway spaces amphipods may stop in, every space except those right outside a room.
+const HALLWAY_STOPS: [usize; 7] = [0, 1, 3, 5, 7, 9, 10];
+
+/// The rows the second part unfolds between the top and bottom rows of the rooms.
+const FOLDED_ROWS: [&str; 2] = ["  #D#C#B#A#", "  #D#B#A#C#"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Amphipod {
+    Amber,
+    Bronze,
+    Copper,
+    Desert,
+}
+
+impl Amphipod {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            'A' => Some(Self::Amber),
+            'B' => Some(Self::Bronze),
+            'C' => Some(Self::Copper),
+            'D' => Some(Self::Desert),
+            _ => None,
+        }
+    }
+
+    fn to_char(self) -> char {
+        match self {
+            Self::Amber => 'A',
+            Self::Bronze => 'B',
+            Self::Copper => 'C',
+            Self::Desert => 'D',
+        }
+    }
+
+    /// The room the amphipod has to end up in, counting from the left.
+    pub fn room(self) -> usize {
+        self as usize
+    }
+
+    pub fn energy_per_step(self) -> u64 {
+        10u64.pow(self as u32)
+    }
+}
+
+/// A space in the burrow, rooms are counted from the left and their spaces from the top.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Location {
+    Hallway(usize),
+    Room { room: usize, depth: usize },
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Hallway(x) => write!(f, "hallway {}", x),
+            Self::Room { room, depth } => write!(f, "room {} space {}", room, depth),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub amphipod: Amphipod,
+    pub from: Location,
+    pub to: Location,
+    pub energy: u64,
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} from {} to {} ({} energy)",
+            self.amphipod.to_char(),
+            self.from,
+            self.to,
+            self.energy
+        )
+    }
+}
+
+/// Where every amphipod is, used as the state of the search.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Burrow {
+    hallway: [Option<Amphipod>; HALLWAY_LENGTH],
+    rooms: [Vec<Option<Amphipod>>; 4],
+}
+
+fn entrance(room: usize) -> usize {
+    2 + 2 * room
+}
+
+impl Burrow {
+    pub fn depth(&self) -> usize {
+        self.rooms[0].len()
+    }
+
+    /// Whether the room only holds amphipods that belong there, so they never have to leave and
+    /// others of their type may enter.
+    fn is_settled(&self, room: usize) -> bool {
+        self.rooms[room]
+            .iter()
+            .all(|space| space.is_none_or(|amphipod| amphipod.room() == room))
+    }
+
+    pub fn is_organized(&self) -> bool {
+        self.hallway.iter().all(Option::is_none)
+            && (0..4).all(|room| {
+                self.rooms[room]
+                    .iter()
+                    .all(|space| space.is_some_and(|amphipod| amphipod.room() == room))
+            })
+    }
+
+    /// Whether the hallway is empty from `from` to `to`, not counting `from` itself.
+    fn is_clear(&self, from: usize, to: usize) -> bool {
+        let range = if from < to {
+            from + 1..=to
+        } else {
+            to..=from - 1
+        };
+
+        self.hallway[range].iter().all(Option::is_none)
+    }
+
+    /// Every legal move with the burrow it leads to. Amphipods move from a room to a hallway stop,
+    /// or from the hallway into their own room once it only holds their own type.
+    pub fn moves(&self) -> Vec<(Move, Burrow)> {
+        let mut moves = vec![];
+
+        for (x, amphipod) in self.hallway.iter().enumerate() {
+            let amphipod = match amphipod {
+                Some(amphipod) => *amphipod,
+                None => continue,
+            };
+            let room = amphipod.room();
+
+            if !self.is_settled(room) || !self.is_clear(x, entrance(room)) {
+                continue;
+            }
+
+            // Settled rooms fill up from the bottom, so the deepest free space is the last one
+            let depth = match self.rooms[room].iter().rposition(Option::is_none) {
+                Some(depth) => depth,
+                None => continue,
+            };
+
+            let mut next = self.clone();
+            next.hallway[x] = None;
+            next.rooms[room][depth] = Some(amphipod);
+
+            let steps = x.abs_diff(entrance(room)) + depth + 1;
+            moves.push((
+                Move {
+                    amphipod,
+                    from: Location::Hallway(x),
+                    to: Location::Room { room, depth },
+                    energy: steps as u64 * amphipod.energy_per_step(),
+                },
+                next,
+            ));
+        }
+
+        for room in 0..4 {
+            if self.is_settled(room) {
+                continue;
+            }
+
+            let (depth, amphipod) = match self.rooms[room]
+                .iter()
+                .enumerate()
+                .find_map(|(depth, space)| space.map(|amphipod| (depth, amphipod)))
+            {
+                Some(top) => top,
+                None => continue,
+            };
+
+            for &stop in HALLWAY_STOPS.iter() {
+                if !self.is_clear(entrance(room), stop) {
+                    continue;
+                }
+
+                let mut next = self.clone();
+                next.rooms[room][depth] = None;
+                next.hallway[stop] = Some(amphipod);
+
+                let steps = depth + 1 + stop.abs_diff(entrance(room));
+                moves.push((
+                    Move {
+                        amphipod,
+                        from: Location::Room { room, depth },
+                        to: Location::Hallway(stop),
+                        energy: steps as u64 * amphipod.energy_per_step(),
+                    },
+                    next,
+                ));
+            }
+        }
+
+        moves
+    }
+
+    /// The burrow with the two rows from the folded part of the diagram inserted in every room.
+    pub fn unfold(&self) -> Burrow {
+        let mut unfolded = self.clone();
+
+        for (room, spaces) in unfolded.rooms.iter_mut().enumerate() {
+            let folded = FOLDED_ROWS
+                .iter()
+                .map(|row| Amphipod::from_char(row.as_bytes()[3 + 2 * room] as char));
+            spaces.splice(1..1, folded);
+        }
+
+        unfolded
+    }
+}
+
+impl Display for Burrow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let space = |amphipod: &Option<Amphipod>| amphipod.map_or('.', Amphipod::to_char);
+
+        writeln!(f, "#############")?;
+        writeln!(
+            f,
+            "#{}#",
+            self.hallway.iter().map(space).collect::<String>()
+        )?;
+
+        for depth in 0..self.depth() {
+            let (edge, end) = if depth == 0 {
+                ("###", "###")
+            } else {
+                ("  #", "#")
+            };
+            let spaces = self
+                .rooms
+                .iter()
+                .map(|room| space(&room[depth]).to_string())
+                .collect::<Vec<_>>();
+
+            writeln!(f, "{}{}{}", edge, spaces.join("#"), end)?;
+        }
+
+        writeln!(f, "  #########")
+    }
+}
+
+/// The cheapest way to organize the amphipods, with the burrow before and after every move.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution {
+    pub energy: u64,
+    pub moves: Vec<Move>,
+    pub burrows: Vec<Burrow>,
+}
+
+/// Search the moves for the least energy that organizes the amphipods, or `None` when they can't
+/// be organized.
+pub fn organize(burrow: &Burrow) -> Option<Solution> {
+    let path = dijkstra(burrow.clone(), Burrow::is_organized, |burrow| {
+        burrow
+            .moves()
+            .into_iter()
+            .map(|(step, next)| (next, step.energy))
+    })?;
+
+    let moves = path
+        .nodes
+        .windows(2)
+        .map(|pair| {
+            pair[0]
+                .moves()
+                .into_iter()
+                .filter(|(_, next)| *next == pair[1])
+                .map(|(step, _)| step)
+                .min_by_key(|step| step.energy)
+                .expect("consecutive burrows are one move apart")
+        })
+        .collect();
+
+    Some(Solution {
+        energy: path.cost,
+        moves,
+        burrows: path.nodes,
+    })
+}
+
+fn parse_space(input: &str, line: &str, x: usize) -> Result<Option<Amphipod>, ParseError> {
+    let token = line
+        .get(x..x + 1)
+        .ok_or_else(|| ParseError::unexpected(input, line, "a row like `  #A#B#C#D#`"))?;
+
+    match token {
+        "." => Ok(None),
+        _ => Amphipod::from_char(token.chars().next().unwrap_or_default())
+            .map(Some)
+            .ok_or_else(|| ParseError::unexpected(input, token, "an amphipod or `.`")),
+    }
+}
+
+#[aoc_generator(day23)]
+pub fn input_generator(input: &str) -> Result<Burrow, ParseError> {
+    let lines = input.lines().collect::<Vec<_>>();
+
+    let hallway_line = lines
+        .get(1)
+        .ok_or_else(|| ParseError::unexpected_end(input, "the hallway"))?;
+    let mut hallway = [None; HALLWAY_LENGTH];
+    for (x, space) in hallway.iter_mut().enumerate() {
+        *space = parse_space(input, hallway_line, x + 1)?;
+    }
+
+    // The room rows end at the closing line of the diagram
+    let room_lines = lines
+        .iter()
+        .skip(2)
+        .take_while(|line| line.trim() != "#########")
+        .collect::<Vec<_>>();
+
+    if room_lines.is_empty() {
+        return Err(ParseError::unexpected_end(input, "the rooms"));
+    }
+
+    let mut rooms: [Vec<Option<Amphipod>>; 4] = Default::default();
+    for line in &room_lines {
+        for (room, spaces) in rooms.iter_mut().enumerate() {
+            spaces.push(parse_space(input, line, 3 + 2 * room)?);
+        }
+    }
+
+    let burrow = Burrow { hallway, rooms };
+
+    let counts = burrow
+        .hallway
+        .iter()
+        .chain(burrow.rooms.iter().flatten())
+        .flatten()
+        .fold([0; 4], |mut counts, amphipod| {
+            counts[amphipod.room()] += 1;
+            counts
+        });
+
+    if counts.iter().any(|&count| count != burrow.depth()) {
+        return Err(ParseError::unexpected(
+            input,
+            room_lines[0],
+            "as many amphipods of every type as fit in a room",
+        ));
+    }
+
+    Ok(burrow)
+}
+
+#[aoc(day23, part1)]
+pub fn solve_part1(input: &Burrow) -> u64 {
+    organize(input)
+        .expect("the amphipods can be organized")
+        .energy
+}
+
+#[aoc(day23, part2)]
+pub fn solve_part2(input: &Burrow) -> u64 {
+    organize(&input.unfold())
+        .expect("the amphipods can be organized")
+        .energy
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// A burrow with rooms of any depth and the amphipods spread over it, reachable or not.
+    fn burrow() -> impl Strategy<Value = Burrow> {
+        (1..=4usize).prop_flat_map(|depth| {
+            let amphipods = [
+                Amphipod::Amber,
+                Amphipod::Bronze,
+                Amphipod::Copper,
+                Amphipod::Desert,
+            ]
+            .iter()
+            .flat_map(|&amphipod| vec![Some(amphipod); depth])
+            .chain(vec![None; HALLWAY_LENGTH])
+            .collect::<Vec<_>>();
+
+            Just(amphipods).prop_shuffle().prop_map(move |shuffled| {
+                let (hallway_spaces, room_spaces) = shuffled.split_at(HALLWAY_LENGTH);
+                let mut hallway = [None; HALLWAY_LENGTH];
+                hallway.copy_from_slice(hallway_spaces);
+
+                let mut rooms: [Vec<Option<Amphipod>>; 4] = Default::default();
+                for (room, spaces) in rooms.iter_mut().zip(room_spaces.chunks(depth)) {
+                    room.extend_from_slice(spaces);
+                }
+
+                Burrow { hallway, rooms }
+            })
+        })
+    }
+
+    fn example() -> Burrow {
+        input_generator(include_str!("../examples/2021/day23/example.txt").trim_end()).unwrap()
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let input = include_str!("../examples/2021/day23/example.txt");
+
+        assert_eq!(input, example().to_string());
+        assert_eq!(example(), input_generator(&example().to_string()).unwrap());
+    }
+
+    #[test]
+    fn test_unfold() {
+        let unfolded = example().unfold().to_string();
+
+        assert_eq!(
+            "  #D#C#B#A#\n  #D#B#A#C#\n  #A#D#C#A#",
+            unfolded
+                .lines()
+                .skip(3)
+                .take(3)
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_organize_moves() {
+        // An Amber and a Bronze amphipod are swapped in the top row, so one of them has to wait in
+        // the hallway while the other goes home
+        let burrow = input_generator(
+            "#############\n#...........#\n###B#A#C#D###\n  #A#B#C#D#\n  #########",
+        )
+        .unwrap();
+        let solution = organize(&burrow).unwrap();
+
+        assert_eq!(46, solution.energy);
+        assert_eq!(solution.moves.len() + 1, solution.burrows.len());
+        assert_eq!(
+            solution.energy,
+            solution.moves.iter().map(|step| step.energy).sum::<u64>()
+        );
+        assert!(solution.burrows.last().unwrap().is_organized());
+    }
+
+    #[test]
+    fn test_invalid_burrow() {
+        assert_eq!(
+            "line 3, column 1: expected as many amphipods of every type as fit in a room, found `###B#C#B#D###`",
+            input_generator("#############\n#...........#\n###B#C#B#D###\n  #A#D#C#C#\n  #########")
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_burrow_round_trip(burrow in burrow()) {
+            prop_assert_eq!(Ok(burrow.clone()), input_generator(&burrow.to_string()));
+        }
+    }
+}
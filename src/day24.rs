@@ -0,0 +1,556 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use crate::answer::Answer;
+use crate::error::{parse_number, ParseError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Register {
+    W,
+    X,
+    Y,
+    Z,
+}
+
+impl Display for Register {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Self::W => 'w',
+            Self::X => 'x',
+            Self::Y => 'y',
+            Self::Z => 'z',
+        };
+
+        write!(f, "{}", c)
+    }
+}
+
+impl FromStr for Register {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "w" => Ok(Self::W),
+            "x" => Ok(Self::X),
+            "y" => Ok(Self::Y),
+            "z" => Ok(Self::Z),
+            _ => Err(ParseError::unexpected(
+                s,
+                s,
+                "a register `w`, `x`, `y` or `z`",
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    Register(Register),
+    Number(i64),
+}
+
+impl Display for Operand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Register(register) => write!(f, "{}", register),
+            Self::Number(number) => write!(f, "{}", number),
+        }
+    }
+}
+
+impl FromStr for Operand {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse() {
+            Ok(register) => Ok(Self::Register(register)),
+            Err(_) => parse_number(s, s).map(Self::Number),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Inp(Register),
+    Add(Register, Operand),
+    Mul(Register, Operand),
+    Div(Register, Operand),
+    Mod(Register, Operand),
+    Eql(Register, Operand),
+}
+
+impl Instruction {
+    /// The registers whose value the instruction depends on. Multiplying by zero doesn't depend
+    /// on the register, which is how programs clear them.
+    fn reads(&self) -> Vec<Register> {
+        match *self {
+            Self::Inp(_) | Self::Mul(_, Operand::Number(0)) => vec![],
+            Self::Add(a, b)
+            | Self::Mul(a, b)
+            | Self::Div(a, b)
+            | Self::Mod(a, b)
+            | Self::Eql(a, b) => match b {
+                Operand::Register(b) => vec![a, b],
+                Operand::Number(_) => vec![a],
+            },
+        }
+    }
+
+    fn writes(&self) -> Register {
+        match *self {
+            Self::Inp(a)
+            | Self::Add(a, _)
+            | Self::Mul(a, _)
+            | Self::Div(a, _)
+            | Self::Mod(a, _)
+            | Self::Eql(a, _) => a,
+        }
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Inp(a) => write!(f, "inp {}", a),
+            Self::Add(a, b) => write!(f, "add {} {}", a, b),
+            Self::Mul(a, b) => write!(f, "mul {} {}", a, b),
+            Self::Div(a, b) => write!(f, "div {} {}", a, b),
+            Self::Mod(a, b) => write!(f, "mod {} {}", a, b),
+            Self::Eql(a, b) => write!(f, "eql {} {}", a, b),
+        }
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = s.split(' ');
+        let operation = tokens.next().unwrap_or_default();
+        let mut next = |expected| {
+            tokens
+                .next()
+                .ok_or_else(|| ParseError::unexpected_end(s, expected))
+        };
+
+        let a = next("a register")?;
+        let a = a.parse().map_err(|e: ParseError| e.within(s, a))?;
+
+        let instruction = if operation == "inp" {
+            Self::Inp(a)
+        } else {
+            let b = next("a register or a number")?;
+            let b = b.parse().map_err(|e: ParseError| e.within(s, b))?;
+
+            match operation {
+                "add" => Self::Add(a, b),
+                "mul" => Self::Mul(a, b),
+                "div" => Self::Div(a, b),
+                "mod" => Self::Mod(a, b),
+                "eql" => Self::Eql(a, b),
+                _ => {
+                    return Err(ParseError::unexpected(
+                        s,
+                        operation,
+                        "`inp`, `add`, `mul`, `div`, `mod` or `eql`",
+                    ))
+                }
+            }
+        };
+
+        if let Some(token) = tokens.next() {
+            return Err(ParseError::unexpected(
+                s,
+                token,
+                "the end of the instruction",
+            ));
+        }
+
+        Ok(instruction)
+    }
+}
+
+/// An instruction the ALU cannot execute, which makes the program invalid for its input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AluError {
+    MissingInput,
+    DivisionByZero,
+    /// Taking the modulo of a negative number or by a number that isn't positive.
+    InvalidModulo,
+    /// An addition, multiplication or division that doesn't fit in 64 bits.
+    Overflow,
+}
+
+impl Display for AluError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingInput => write!(f, "ran out of input"),
+            Self::DivisionByZero => write!(f, "division by zero"),
+            Self::InvalidModulo => write!(f, "modulo of a negative number or by a number below 1"),
+            Self::Overflow => write!(f, "arithmetic overflow"),
+        }
+    }
+}
+
+impl Error for AluError {}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Alu {
+    registers: [i64; 4],
+}
+
+impl Alu {
+    pub fn get(&self, register: Register) -> i64 {
+        self.registers[register as usize]
+    }
+
+    fn value(&self, operand: Operand) -> i64 {
+        match operand {
+            Operand::Register(register) => self.get(register),
+            Operand::Number(number) => number,
+        }
+    }
+
+    pub fn execute(
+        &mut self,
+        instruction: &Instruction,
+        input: &mut impl Iterator<Item = i64>,
+    ) -> Result<(), AluError> {
+        let value = match *instruction {
+            Instruction::Inp(_) => input.next().ok_or(AluError::MissingInput)?,
+            Instruction::Add(a, b) => self
+                .get(a)
+                .checked_add(self.value(b))
+                .ok_or(AluError::Overflow)?,
+            Instruction::Mul(a, b) => self
+                .get(a)
+                .checked_mul(self.value(b))
+                .ok_or(AluError::Overflow)?,
+            Instruction::Div(a, b) => match self.value(b) {
+                0 => return Err(AluError::DivisionByZero),
+                // Only dividing the lowest number by -1 overflows
+                b => self.get(a).checked_div(b).ok_or(AluError::Overflow)?,
+            },
+            Instruction::Mod(a, b) => {
+                let (a, b) = (self.get(a), self.value(b));
+
+                if a < 0 || b <= 0 {
+                    return Err(AluError::InvalidModulo);
+                }
+
+                a % b
+            }
+            Instruction::Eql(a, b) => (self.get(a) == self.value(b)) as i64,
+        };
+
+        self.registers[instruction.writes() as usize] = value;
+        Ok(())
+    }
+
+    /// Run the program from the current state, reading `inp` values from `input` in order.
+    pub fn run<'a>(
+        &mut self,
+        program: impl IntoIterator<Item = &'a Instruction>,
+        input: impl IntoIterator<Item = i64>,
+    ) -> Result<(), AluError> {
+        let mut input = input.into_iter();
+
+        for instruction in program {
+            self.execute(instruction, &mut input)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether MONAD accepts the model number: every digit is 1 to 9, there is one for every `inp`
+/// and the program ends with 0 in `z`.
+pub fn is_valid(program: &[Instruction], model_number: u64) -> bool {
+    let digits = model_number
+        .to_string()
+        .bytes()
+        .map(|digit| (digit - b'0') as i64)
+        .collect::<Vec<_>>();
+    let inputs = program
+        .iter()
+        .filter(|instruction| matches!(instruction, Instruction::Inp(_)))
+        .count();
+
+    if digits.len() != inputs || digits.contains(&0) {
+        return false;
+    }
+
+    let mut alu = Alu::default();
+    alu.run(program, digits).is_ok() && alu.get(Register::Z) == 0
+}
+
+/// The instructions from one `inp` up to the next, with the registers it doesn't overwrite before
+/// reading, including the ones it never touches since later blocks may still read those.
+struct Block<'a> {
+    instructions: &'a [Instruction],
+    live: [bool; 4],
+}
+
+impl<'a> Block<'a> {
+    fn new(instructions: &'a [Instruction]) -> Self {
+        let mut live = [true; 4];
+        let mut read = [false; 4];
+
+        for instruction in instructions {
+            for register in instruction.reads() {
+                read[register as usize] = true;
+            }
+
+            let written = instruction.writes() as usize;
+            live[written] &= read[written];
+            read[written] = true;
+        }
+
+        Self { instructions, live }
+    }
+
+    /// The state with every register the block overwrites before reading cleared, so states that
+    /// only differ in those are searched once.
+    fn canonical(&self, mut alu: Alu) -> Alu {
+        for (value, &live) in alu.registers.iter_mut().zip(self.live.iter()) {
+            if !live {
+                *value = 0;
+            }
+        }
+
+        alu
+    }
+}
+
+/// Search the digits one block at a time, trying them in the given order so the first valid
+/// number found is the one wanted. States a block has failed from are remembered, since many
+/// different prefixes lead to the same state.
+fn search(
+    blocks: &[Block],
+    index: usize,
+    alu: Alu,
+    digits: &[i64],
+    failed: &mut HashSet<(usize, Alu)>,
+) -> Option<u64> {
+    let block = match blocks.get(index) {
+        Some(block) => block,
+        None => return Some(0).filter(|_| alu.get(Register::Z) == 0),
+    };
+
+    let alu = block.canonical(alu);
+    if failed.contains(&(index, alu)) {
+        return None;
+    }
+
+    for &digit in digits {
+        let mut next = alu;
+
+        if next.run(block.instructions, Some(digit)).is_err() {
+            continue;
+        }
+
+        if let Some(rest) = search(blocks, index + 1, next, digits, failed) {
+            let remaining = (blocks.len() - index - 1) as u32;
+            return Some(digit as u64 * 10u64.pow(remaining) + rest);
+        }
+    }
+
+    failed.insert((index, alu));
+    None
+}
+
+fn find_model_number(program: &[Instruction], digits: &[i64]) -> Option<u64> {
+    let start = program
+        .iter()
+        .position(|instruction| matches!(instruction, Instruction::Inp(_)))
+        .unwrap_or(program.len());
+
+    // Anything before the first input only has to run once
+    let mut alu = Alu::default();
+    alu.run(&program[..start], None).ok()?;
+
+    let mut blocks = vec![];
+    let mut rest = &program[start..];
+
+    while !rest.is_empty() {
+        let end = rest[1..]
+            .iter()
+            .position(|instruction| matches!(instruction, Instruction::Inp(_)))
+            .map_or(rest.len(), |end| end + 1);
+
+        blocks.push(Block::new(&rest[..end]));
+        rest = &rest[end..];
+    }
+
+    search(&blocks, 0, alu, digits, &mut HashSet::new())
+}
+
+pub fn largest_model_number(program: &[Instruction]) -> Option<u64> {
+    find_model_number(program, &[9, 8, 7, 6, 5, 4, 3, 2, 1])
+}
+
+pub fn smallest_model_number(program: &[Instruction]) -> Option<u64> {
+    find_model_number(program, &[1, 2, 3, 4, 5, 6, 7, 8, 9])
+}
+
+#[aoc_generator(day24)]
+pub fn input_generator(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    input
+        .lines()
+        .map(|line| line.parse().map_err(|e: ParseError| e.within(input, line)))
+        .collect()
+}
+
+/// The model number as the answer, a program that rejects every model number gets an answer
+/// saying so instead.
+fn model_number_answer(model_number: Option<u64>) -> Answer {
+    model_number.map_or_else(|| Answer::from("no valid model number"), Answer::from)
+}
+
+#[aoc(day24, part1)]
+pub fn solve_part1(input: &[Instruction]) -> Answer {
+    model_number_answer(largest_model_number(input))
+}
+
+#[aoc(day24, part2)]
+pub fn solve_part2(input: &[Instruction]) -> Answer {
+    model_number_answer(smallest_model_number(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn register() -> impl Strategy<Value = Register> {
+        prop_oneof![
+            Just(Register::W),
+            Just(Register::X),
+            Just(Register::Y),
+            Just(Register::Z)
+        ]
+    }
+
+    fn instruction() -> impl Strategy<Value = Instruction> {
+        let operand = prop_oneof![
+            register().prop_map(Operand::Register),
+            any::<i64>().prop_map(Operand::Number)
+        ];
+
+        prop_oneof![
+            register().prop_map(Instruction::Inp),
+            (register(), operand.clone()).prop_map(|(a, b)| Instruction::Add(a, b)),
+            (register(), operand.clone()).prop_map(|(a, b)| Instruction::Mul(a, b)),
+            (register(), operand.clone()).prop_map(|(a, b)| Instruction::Div(a, b)),
+            (register(), operand.clone()).prop_map(|(a, b)| Instruction::Mod(a, b)),
+            (register(), operand).prop_map(|(a, b)| Instruction::Eql(a, b)),
+        ]
+    }
+
+    fn digits() -> Vec<Instruction> {
+        input_generator(include_str!("../examples/2021/day24/digits.txt").trim_end()).unwrap()
+    }
+
+    #[test]
+    fn test_run() {
+        let program = input_generator(
+            "inp w\nadd z w\nmod z 2\ndiv w 2\nadd y w\nmod y 2\ndiv w 2\nadd x w\nmod x 2\ndiv w 2\nmod w 2",
+        )
+        .unwrap();
+        let mut alu = Alu::default();
+        alu.run(&program, Some(13)).unwrap();
+
+        assert_eq!(
+            vec![1, 1, 0, 1],
+            [Register::W, Register::X, Register::Y, Register::Z]
+                .iter()
+                .map(|&register| alu.get(register))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_alu_errors() {
+        let program = input_generator("inp x\nmod x 0").unwrap();
+
+        assert_eq!(
+            Err(AluError::MissingInput),
+            Alu::default().run(&program, None)
+        );
+        assert_eq!(
+            Err(AluError::InvalidModulo),
+            Alu::default().run(&program, Some(5))
+        );
+        assert_eq!(
+            Err(AluError::DivisionByZero),
+            Alu::default().run(&input_generator("div x 0").unwrap(), None)
+        );
+        assert_eq!(
+            Err(AluError::Overflow),
+            Alu::default().run(
+                &input_generator("add x 9223372036854775807\nadd x 1").unwrap(),
+                None
+            )
+        );
+        assert_eq!(
+            Err(AluError::Overflow),
+            Alu::default().run(
+                &input_generator("add x -9223372036854775808\ndiv x -1").unwrap(),
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn test_is_valid() {
+        assert!(is_valid(&digits(), 96));
+        assert!(!is_valid(&digits(), 95));
+        assert!(!is_valid(&digits(), 30));
+        assert!(!is_valid(&digits(), 963));
+    }
+
+    #[test]
+    fn test_live_registers() {
+        let program = input_generator("inp w\nmul x 0\nadd x z\nmod x 26\nadd y w").unwrap();
+
+        assert_eq!([false, false, true, true], Block::new(&program).live);
+    }
+
+    #[test]
+    fn test_untouched_registers_stay_live() {
+        // `y` is only set in the first block and only read in the last, so the middle block
+        // must keep it
+        let program = input_generator("inp w\nadd y w\ninp w\ninp w\nadd z y\nadd z -5").unwrap();
+
+        assert_eq!([false, true, true, true], Block::new(&program[2..3]).live);
+        assert_eq!(Some(599), largest_model_number(&program));
+        assert_eq!(Some(511), smallest_model_number(&program));
+    }
+
+    #[test]
+    fn test_no_valid_model_number() {
+        // `z` ends up as the digit, which is never zero
+        let program = input_generator("inp w\nadd z w").unwrap();
+
+        assert_eq!(Answer::from("no valid model number"), solve_part1(&program));
+        assert_eq!(Answer::from("no valid model number"), solve_part2(&program));
+    }
+
+    #[test]
+    fn test_invalid_instruction() {
+        assert_eq!(
+            "line 2, column 5: expected a register `w`, `x`, `y` or `z`, found `a`",
+            input_generator("inp w\nadd a 1").unwrap_err().to_string()
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_instruction_round_trip(instruction in instruction()) {
+            prop_assert_eq!(Ok(instruction), instruction.to_string().parse());
+        }
+    }
+}
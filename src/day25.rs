@@ -0,0 +1,193 @@
+use std::fmt::{self, Display, Formatter};
+use std::iter;
+
+use crate::error::ParseError;
+use crate::grid::Grid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Empty,
+    East,
+    South,
+}
+
+impl Display for Cell {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Self::Empty => '.',
+            Self::East => '>',
+            Self::South => 'v',
+        };
+
+        write!(f, "{}", c)
+    }
+}
+
+/// The sea cucumbers on a floor that wraps around at the edges.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeaFloor {
+    cells: Grid<Cell>,
+}
+
+impl SeaFloor {
+    pub fn new(cells: Grid<Cell>) -> Self {
+        Self { cells }
+    }
+
+    /// Move every cucumber of the herd whose next cell is empty, all at once. Returns the number
+    /// that moved.
+    fn move_herd(&mut self, herd: Cell, (dx, dy): (usize, usize)) -> usize {
+        let (width, height) = (self.cells.width(), self.cells.height());
+
+        let moving = self
+            .cells
+            .iter()
+            .filter(|&(_, &cell)| cell == herd)
+            .map(|((x, y), _)| ((x, y), ((x + dx) % width, (y + dy) % height)))
+            .filter(|&(_, next)| self.cells[next] == Cell::Empty)
+            .collect::<Vec<_>>();
+
+        for &(from, to) in &moving {
+            self.cells[from] = Cell::Empty;
+            self.cells[to] = herd;
+        }
+
+        moving.len()
+    }
+
+    /// One step, the east-facing herd moves first and then the south-facing herd. Returns the
+    /// floor after the step and the number of cucumbers that moved.
+    pub fn step(&self) -> (SeaFloor, usize) {
+        let mut next = self.clone();
+        let moved = next.move_herd(Cell::East, (1, 0)) + next.move_herd(Cell::South, (0, 1));
+
+        (next, moved)
+    }
+
+    /// The floor before any steps followed by the floor after every step, ending once no
+    /// cucumber moves.
+    pub fn steps(&self) -> impl Iterator<Item = SeaFloor> {
+        iter::successors(Some((self.clone(), true)), |(floor, moving)| {
+            if !moving {
+                return None;
+            }
+
+            let (next, moved) = floor.step();
+            Some((next, moved > 0))
+        })
+        .map(|(floor, _)| floor)
+    }
+
+    /// The first step on which no sea cucumber moves.
+    pub fn stationary_step(&self) -> usize {
+        self.steps().skip(1).count()
+    }
+}
+
+impl Display for SeaFloor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.cells)
+    }
+}
+
+#[aoc_generator(day25)]
+pub fn input_generator(input: &str) -> Result<SeaFloor, ParseError> {
+    let rows = input.lines().collect::<Vec<_>>();
+    let width = rows.first().map_or(0, |row| row.len());
+    let mut cells = Vec::with_capacity(width * rows.len());
+
+    for row in &rows {
+        if row.len() != width {
+            return Err(ParseError::unexpected(input, row, "rows of equal width"));
+        }
+
+        for (index, c) in row.char_indices() {
+            cells.push(match c {
+                '.' => Cell::Empty,
+                '>' => Cell::East,
+                'v' => Cell::South,
+                _ => {
+                    return Err(ParseError::unexpected(
+                        input,
+                        &row[index..index + c.len_utf8()],
+                        "`>`, `v` or `.`",
+                    ))
+                }
+            });
+        }
+    }
+
+    if cells.is_empty() {
+        return Err(ParseError::unexpected_end(input, "the sea floor"));
+    }
+
+    Ok(SeaFloor::new(Grid::new(width, rows.len(), cells)))
+}
+
+#[aoc(day25, part1)]
+pub fn solve_part1(input: &SeaFloor) -> usize {
+    input.stationary_step()
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn sea_floor() -> impl Strategy<Value = SeaFloor> {
+        let cell = prop::sample::select(vec![Cell::Empty, Cell::East, Cell::South]);
+
+        (1..10usize, 1..10usize).prop_flat_map(move |(width, height)| {
+            prop::collection::vec(cell.clone(), width * height)
+                .prop_map(move |cells| SeaFloor::new(Grid::new(width, height, cells)))
+        })
+    }
+
+    #[test]
+    fn test_wrapping_row() {
+        let floor = input_generator("...>>>>>...").unwrap();
+        let rendered = floor
+            .steps()
+            .skip(1)
+            .take(2)
+            .map(|floor| floor.to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec!["...>>>>.>..\n", "...>>>.>.>.\n"], rendered);
+        assert_eq!(
+            ">..\n...\nv..\n",
+            input_generator("..>\n...\nv..")
+                .unwrap()
+                .step()
+                .0
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_step() {
+        let floor =
+            input_generator(include_str!("../examples/2021/day25/example.txt").trim_end()).unwrap();
+
+        assert_eq!(
+            "....>.>v.>\nv.v>.>v.v.\n>v>>..>v..\n>>v>v>.>.v\n.>v.v...v.\nv>>.>vvv..\n..v...>>..\nvv...>>vv.\n>.v.v..v.v\n",
+            floor.step().0.to_string()
+        );
+    }
+
+    #[test]
+    fn test_invalid_floor() {
+        assert_eq!(
+            "line 2, column 2: expected `>`, `v` or `.`, found `<`",
+            input_generator("..\n.<").unwrap_err().to_string()
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_sea_floor_round_trip(floor in sea_floor()) {
+            prop_assert_eq!(Ok(floor.clone()), input_generator(&floor.to_string()));
+        }
+    }
+}
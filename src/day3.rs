@@ -1,17 +1,36 @@
+use crate::error::ParseError;
+
 pub struct Input {
     number_width: usize,
     numbers: Vec<usize>,
 }
 
 #[aoc_generator(day3)]
-pub fn input_generator(input: &str) -> Input {
-    Input {
-        number_width: input.lines().next().unwrap().len(),
+pub fn input_generator(input: &str) -> Result<Input, ParseError> {
+    let number_width = input
+        .lines()
+        .next()
+        .ok_or_else(|| ParseError::unexpected_end(input, "a binary number"))?
+        .len();
+
+    Ok(Input {
+        number_width,
         numbers: input
             .lines()
-            .map(|line| usize::from_str_radix(line, 2).unwrap())
-            .collect(),
-    }
+            .map(|line| {
+                if line.len() != number_width {
+                    return Err(ParseError::unexpected(
+                        input,
+                        line,
+                        "numbers of equal width",
+                    ));
+                }
+
+                usize::from_str_radix(line, 2)
+                    .map_err(|_| ParseError::unexpected(input, line, "a binary number"))
+            })
+            .collect::<Result<_, _>>()?,
+    })
 }
 
 fn divide_by_2_round_up(value: usize) -> usize {
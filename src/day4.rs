@@ -1,9 +1,12 @@
 #![allow(unused_results)]
 
 use std::fmt::{Display, Formatter};
-use std::num::ParseIntError;
 use std::str::FromStr;
 
+use crate::error::{parse_number, ParseError};
+use crate::grid::Grid;
+use crate::visualize::{Color, Frame, Visualize};
+
 const BOARD_WIDTH: usize = 5;
 const BOARD_HEIGHT: usize = 5;
 
@@ -11,23 +14,24 @@ const COLOR_RED: &str = "\x1b[0;31m";
 const COLOR_GREEN: &str = "\x1b[0;32m";
 const COLOR_RESET: &str = "\x1b[0;0m";
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Number {
     value: usize,
     marked: bool,
 }
 
 impl FromStr for Number {
-    type Err = ParseIntError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(Self {
-            value: s.parse::<usize>()?,
+            value: parse_number(s, s)?,
             marked: false,
         })
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Board {
     numbers: Vec<Number>,
 }
@@ -106,22 +110,27 @@ impl Display for Board {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Bingo {
     numbers: Vec<usize>,
     boards: Vec<Board>,
 }
 
 impl Bingo {
-    pub fn from_input(input: &str) -> Self {
+    pub fn from_input(input: &str) -> Result<Self, ParseError> {
         let mut lines = input.trim_end().lines();
         let numbers = lines
             .next()
-            .unwrap()
+            .ok_or_else(|| ParseError::unexpected_end(input, "the drawn numbers"))?
             .trim()
             .split(',')
-            .map(|c| c.parse::<usize>().unwrap())
-            .collect();
-        lines.next(); // Skip first empty line
+            .map(|c| parse_number(input, c))
+            .collect::<Result<_, _>>()?;
+
+        // Skip first empty line
+        if let Some(line) = lines.next().filter(|line| !line.trim().is_empty()) {
+            return Err(ParseError::unexpected(input, line, "an empty line"));
+        }
 
         let mut boards: Vec<Board> = vec![];
         let mut board_numbers: Vec<Number> = Vec::with_capacity(BOARD_WIDTH * BOARD_HEIGHT);
@@ -129,6 +138,10 @@ impl Bingo {
         for line in lines {
             // Push the board and reset the numbers
             if line.trim().is_empty() {
+                if board_numbers.len() != BOARD_WIDTH * BOARD_HEIGHT {
+                    return Err(ParseError::unexpected(input, line, "another board row"));
+                }
+
                 boards.push(Board {
                     numbers: board_numbers,
                 });
@@ -136,33 +149,124 @@ impl Bingo {
                 continue;
             }
 
-            board_numbers.append(
-                &mut line
-                    .trim()
-                    .split_whitespace()
-                    .map(|c| Number::from_str(c).unwrap())
-                    .collect::<Vec<Number>>(),
-            );
+            if board_numbers.len() == BOARD_WIDTH * BOARD_HEIGHT {
+                return Err(ParseError::unexpected(input, line, "an empty line"));
+            }
+
+            let row = line
+                .split_whitespace()
+                .map(|c| Number::from_str(c).map_err(|e| e.within(input, c)))
+                .collect::<Result<Vec<Number>, _>>()?;
+
+            if row.len() != BOARD_WIDTH {
+                return Err(ParseError::unexpected(
+                    input,
+                    line,
+                    "a board row of 5 numbers",
+                ));
+            }
+
+            board_numbers.extend(row);
         }
 
         // Construct final board
+        if board_numbers.len() != BOARD_WIDTH * BOARD_HEIGHT {
+            return Err(ParseError::unexpected_end(input, "another board row"));
+        }
+
         boards.push(Board {
             numbers: board_numbers,
         });
 
-        Self { numbers, boards }
+        Ok(Self { numbers, boards })
+    }
+
+    /// The game as puzzle input, which leaves out any marks.
+    pub fn to_input(&self) -> String {
+        let numbers = self
+            .numbers
+            .iter()
+            .map(|number| number.to_string())
+            .collect::<Vec<_>>();
+
+        let boards = self
+            .boards
+            .iter()
+            .map(|board| {
+                board
+                    .numbers
+                    .chunks(BOARD_WIDTH)
+                    .map(|row| {
+                        row.iter()
+                            .map(|number| format!("{:>2}", number.value))
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect::<Vec<_>>();
+
+        format!("{}\n\n{}", numbers.join(","), boards.join("\n\n"))
     }
 }
 
+/// Draw the boards side by side, unmarked numbers in red and marked numbers in green, or in yellow
+/// once the board has won.
+fn render(boards: &[Board], won: &[bool]) -> Frame {
+    let width = (boards.len() * (BOARD_WIDTH + 1)).saturating_sub(1);
+    let mut frame = Grid::filled(width, BOARD_HEIGHT, Color::BLACK);
+
+    for (index, (board, &won)) in boards.iter().zip(won).enumerate() {
+        for (i, number) in board.numbers.iter().enumerate() {
+            let color = match (number.marked, won) {
+                (false, _) => Color::RED,
+                (true, false) => Color::GREEN,
+                (true, true) => Color::YELLOW,
+            };
+
+            frame[(index * (BOARD_WIDTH + 1) + i % BOARD_WIDTH, i / BOARD_WIDTH)] = color;
+        }
+    }
+
+    frame
+}
+
+impl Visualize for Bingo {
+    /// A frame for every drawn number, until every board has won.
+    fn frames(&self) -> Vec<Frame> {
+        let mut boards = self.boards.clone();
+        let mut won = vec![false; boards.len()];
+        let mut frames = vec![render(&boards, &won)];
+
+        for &number in &self.numbers {
+            if won.iter().all(|&won| won) {
+                break;
+            }
+
+            for (board, won) in boards.iter_mut().zip(won.iter_mut()) {
+                *won |= board.mark(number);
+            }
+
+            frames.push(render(&boards, &won));
+        }
+
+        frames
+    }
+}
+
+#[aoc_generator(day4)]
+pub fn input_generator(input: &str) -> Result<Bingo, ParseError> {
+    Bingo::from_input(input)
+}
+
 #[aoc(day4, part1)]
-pub fn solve_part1(input: &str) -> usize {
-    let mut bingo = Bingo::from_input(input);
+pub fn solve_part1(input: &Bingo) -> usize {
+    let mut bingo = input.clone();
 
     for number in &bingo.numbers {
         for board in bingo.boards.iter_mut() {
             if board.mark(*number) {
-                println!("Winning board with number: {} = \n{:#}", number, board);
-
                 return number * board.sum_of_unmarked_numbers();
             }
         }
@@ -172,8 +276,8 @@ pub fn solve_part1(input: &str) -> usize {
 }
 
 #[aoc(day4, part2)]
-pub fn solve_part2(input: &str) -> usize {
-    let bingo = Bingo::from_input(input);
+pub fn solve_part2(input: &Bingo) -> usize {
+    let bingo = input.clone();
 
     let mut boards: Vec<Board> = bingo.boards;
 
@@ -183,12 +287,12 @@ pub fn solve_part2(input: &str) -> usize {
     for number in &bingo.numbers {
         last_number = *number;
 
-        boards.drain_filter(|board| {
+        boards.retain_mut(|board| {
             if board.mark(last_number) {
                 last_winning_unmarked_sum = board.sum_of_unmarked_numbers();
-                true
-            } else {
                 false
+            } else {
+                true
             }
         });
 
@@ -202,87 +306,69 @@ pub fn solve_part2(input: &str) -> usize {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use proptest::prelude::*;
 
-    #[test]
-    fn test_board_gen() {
-        let input = r#"7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+    use super::*;
 
-22 13 17 11  0
- 8  2 23  4 24
-21  9 14 16  7
- 6 10  3 18  5
- 1 12 20 15 19
+    fn bingo() -> impl Strategy<Value = Bingo> {
+        let board =
+            prop::collection::vec(any::<usize>(), BOARD_WIDTH * BOARD_HEIGHT).prop_map(|values| {
+                Board {
+                    numbers: values
+                        .into_iter()
+                        .map(|value| Number {
+                            value,
+                            marked: false,
+                        })
+                        .collect(),
+                }
+            });
 
- 3 15  0  2 22
- 9 18 13 17  5
-19  8  7 25 23
-20 11 10 24  4
-14 21 16 12  6
+        (
+            prop::collection::vec(any::<usize>(), 1..30),
+            prop::collection::vec(board, 1..4),
+        )
+            .prop_map(|(numbers, boards)| Bingo { numbers, boards })
+    }
 
-14 21 17 24  4
-10 16 15  9 19
-18  8 23 26 20
-22 11 13  6  5
- 2  0 12  3  7"#;
+    #[test]
+    fn test_board_gen() {
+        let input = include_str!("../examples/2021/day4/example.txt");
 
-        let bingo = Bingo::from_input(input);
+        let bingo = Bingo::from_input(input).unwrap();
 
         assert_eq!(27, bingo.numbers.len());
         assert_eq!(3, bingo.boards.len())
     }
 
     #[test]
-    fn test_part_1() {
-        let input = r#"7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
-
-22 13 17 11  0
- 8  2 23  4 24
-21  9 14 16  7
- 6 10  3 18  5
- 1 12 20 15 19
-
- 3 15  0  2 22
- 9 18 13 17  5
-19  8  7 25 23
-20 11 10 24  4
-14 21 16 12  6
-
-14 21 17 24  4
-10 16 15  9 19
-18  8 23 26 20
-22 11 13  6  5
- 2  0 12  3  7"#;
-
-        let expected = 4512;
-
-        assert_eq!(expected, solve_part1(input))
+    fn test_frames() {
+        let input = include_str!("../examples/2021/day4/example.txt");
+        let frames = Bingo::from_input(input).unwrap().frames();
+
+        // The last board to win does so on the 15th number
+        assert_eq!(16, frames.len());
+        assert_eq!((17, 5), (frames[0].width(), frames[0].height()));
+        assert!(frames[0]
+            .iter()
+            .all(|((x, _), &color)| color == if x % 6 == 5 { Color::BLACK } else { Color::RED }));
+        assert!(frames[15].values().all(|&color| color != Color::GREEN));
     }
 
     #[test]
-    fn test_part_2() {
-        let input = r#"7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
-
-22 13 17 11  0
- 8  2 23  4 24
-21  9 14 16  7
- 6 10  3 18  5
- 1 12 20 15 19
-
- 3 15  0  2 22
- 9 18 13 17  5
-19  8  7 25 23
-20 11 10 24  4
-14 21 16 12  6
-
-14 21 17 24  4
-10 16 15  9 19
-18  8 23 26 20
-22 11 13  6  5
- 2  0 12  3  7"#;
-
-        let expected = 1924;
-
-        assert_eq!(expected, solve_part2(input))
+    fn test_incomplete_board() {
+        let input = "1,2,3\n\n1 2 3 4 5\n6 7 8 9 10\n\n1 2 3 4 5";
+
+        assert_eq!(
+            "line 5, column 1: expected another board row, found an empty line",
+            Bingo::from_input(input).err().unwrap().to_string()
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_bingo_round_trip(bingo in bingo()) {
+            prop_assert_eq!(Ok(bingo.clone()), Bingo::from_input(&bingo.to_input()));
+        }
     }
 }
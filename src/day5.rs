@@ -1,7 +1,9 @@
 use std::collections::HashMap;
-use std::num::ParseIntError;
+use std::fmt::{self, Display, Formatter};
 use std::str::FromStr;
 
+use crate::error::{parse_number, ParseError};
+
 fn get_range(a: usize, b: usize) -> Vec<usize> {
     if a <= b {
         (a..=b).collect()
@@ -10,25 +12,33 @@ fn get_range(a: usize, b: usize) -> Vec<usize> {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Point {
     x: usize,
     y: usize,
 }
 
 impl FromStr for Point {
-    type Err = ParseIntError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (x, y) = s.split_once(',').unwrap();
+        let (x, y) = s
+            .split_once(',')
+            .ok_or_else(|| ParseError::unexpected(s, s, "a point like `x,y`"))?;
         Ok(Self {
-            x: x.parse()?,
-            y: y.parse()?,
+            x: parse_number(s, x)?,
+            y: parse_number(s, y)?,
         })
     }
 }
 
-#[derive(Debug)]
+impl Display for Point {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Line {
     start: Point,
     end: Point,
@@ -57,17 +67,25 @@ impl Line {
 }
 
 impl FromStr for Line {
-    type Err = ParseIntError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (start, end) = s.split_once(" -> ").unwrap();
+        let (start, end) = s
+            .split_once(" -> ")
+            .ok_or_else(|| ParseError::unexpected(s, s, "a line like `x1,y1 -> x2,y2`"))?;
         Ok(Line {
-            start: start.parse()?,
-            end: end.parse()?,
+            start: start.parse().map_err(|e: ParseError| e.within(s, start))?,
+            end: end.parse().map_err(|e: ParseError| e.within(s, end))?,
         })
     }
 }
 
+impl Display for Line {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} -> {}", self.start, self.end)
+    }
+}
+
 fn calculate_overlaps<'a, It>(lines: It) -> usize
 where
     It: Iterator<Item = &'a Line>,
@@ -84,10 +102,10 @@ where
 }
 
 #[aoc_generator(day5)]
-pub fn input_generator(input: &str) -> Vec<Line> {
+pub fn input_generator(input: &str) -> Result<Vec<Line>, ParseError> {
     input
         .lines()
-        .map(|line| Line::from_str(line).unwrap())
+        .map(|line| Line::from_str(line).map_err(|e| e.within(input, line)))
         .collect()
 }
 
@@ -105,8 +123,18 @@ pub fn solve_part2(input: &[Line]) -> usize {
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
+    fn point() -> impl Strategy<Value = Point> {
+        (any::<usize>(), any::<usize>()).prop_map(|(x, y)| Point { x, y })
+    }
+
+    fn line() -> impl Strategy<Value = Line> {
+        (point(), point()).prop_map(|(start, end)| Line { start, end })
+    }
+
     #[test]
     fn test_generate_points_for_line_x_up() {
         // Line should produce points 0;0 1;0 2;0
@@ -160,24 +188,13 @@ mod tests {
     }
 
     #[test]
-    fn test_part_1() {
-        let input = r#"0,9 -> 5,9
-8,0 -> 0,8
-9,4 -> 3,4
-2,2 -> 2,1
-7,0 -> 7,4
-6,4 -> 2,0
-0,9 -> 2,9
-3,4 -> 1,4
-0,0 -> 8,8
-5,5 -> 8,2
-"#;
-
-        let lines = input_generator(input);
-
-        let result = solve_part1(&lines);
-
-        assert_eq!(5, result)
+    fn test_invalid_line() {
+        let input = "0,9 -> 5,9\n8,0 -> 0,y";
+
+        assert_eq!(
+            "line 2, column 10: expected a number, found `y`",
+            input_generator(input).unwrap_err().to_string()
+        )
     }
 
     #[test]
@@ -205,4 +222,17 @@ mod tests {
         assert_eq!(3, point_c.x);
         assert_eq!(3, point_c.y);
     }
+
+    proptest! {
+        #[test]
+        fn test_lines_round_trip(lines in prop::collection::vec(line(), 1..20)) {
+            let input = lines
+                .iter()
+                .map(|line| line.to_string())
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            prop_assert_eq!(Ok(lines), input_generator(&input));
+        }
+    }
 }
@@ -1,3 +1,5 @@
+use crate::error::{parse_number, ParseError};
+
 pub fn simulate_lanternfish_school(input: &[usize], days: usize) -> usize {
     // Split school on timer
     let mut school: [usize; 9] = [0; 9];
@@ -16,8 +18,14 @@ pub fn simulate_lanternfish_school(input: &[usize], days: usize) -> usize {
 }
 
 #[aoc_generator(day6)]
-pub fn input_generator(input: &str) -> Vec<usize> {
-    input.split(',').map(|x| x.parse().unwrap()).collect()
+pub fn input_generator(input: &str) -> Result<Vec<usize>, ParseError> {
+    input
+        .split(',')
+        .map(|x| match parse_number(input, x)? {
+            timer @ 0..=8 => Ok(timer),
+            _ => Err(ParseError::unexpected(input, x, "a timer between 0 and 8")),
+        })
+        .collect()
 }
 
 #[aoc(day6, part1)]
@@ -36,29 +44,29 @@ mod tests {
 
     #[test]
     fn test_parse_input() {
-        let input = input_generator("3,4,3,1,2");
+        let input = input_generator("3,4,3,1,2").unwrap();
 
         assert_eq!(5, input.len());
     }
 
     #[test]
     fn test_part1_example_18days() {
-        let mut input = input_generator("3,4,3,1,2");
+        let input = input_generator("3,4,3,1,2").unwrap();
 
-        assert_eq!(26, simulate_lanternfish_school(&mut input, 18))
+        assert_eq!(26, simulate_lanternfish_school(&input, 18))
     }
 
     #[test]
     fn test_part1_example_80days() {
-        let mut input = input_generator("3,4,3,1,2");
+        let input = input_generator("3,4,3,1,2").unwrap();
 
-        assert_eq!(5934, simulate_lanternfish_school(&mut input, 80))
+        assert_eq!(5934, simulate_lanternfish_school(&input, 80))
     }
 
     #[test]
     fn test_part2_example_256days() {
-        let mut input = input_generator("3,4,3,1,2");
+        let input = input_generator("3,4,3,1,2").unwrap();
 
-        assert_eq!(26984457539, simulate_lanternfish_school(&mut input, 256))
+        assert_eq!(26984457539, simulate_lanternfish_school(&input, 256))
     }
 }
@@ -1,8 +1,10 @@
+use crate::error::{parse_number, ParseError};
+
 #[aoc_generator(day7)]
-pub fn input_generator(input: &str) -> Vec<isize> {
+pub fn input_generator(input: &str) -> Result<Vec<isize>, ParseError> {
     input
         .split(',')
-        .map(|line| line.parse::<isize>().unwrap())
+        .map(|line| parse_number(input, line))
         .collect()
 }
 
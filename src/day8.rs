@@ -1,5 +1,143 @@
 use std::collections::BTreeSet;
 
+use crate::error::ParseError;
+
+type Digit = BTreeSet<char>;
+
+/// A single display, the ten unique signal patterns ordered by the digit they show and the four
+/// digit output value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    patterns: Vec<Digit>,
+    outputs: Vec<Digit>,
+}
+
+fn parse_digits(
+    input: &str,
+    digits: &str,
+    count: usize,
+    expected: &'static str,
+) -> Result<Vec<Digit>, ParseError> {
+    let words = digits.split_whitespace().collect::<Vec<_>>();
+
+    if words.len() != count {
+        return Err(ParseError::unexpected(input, digits, expected));
+    }
+
+    words
+        .iter()
+        .map(|word| {
+            if word.chars().all(|c| ('a'..='g').contains(&c)) {
+                Ok(word.chars().collect())
+            } else {
+                Err(ParseError::unexpected(input, word, "segments a to g"))
+            }
+        })
+        .collect()
+}
+
+/// Work out which pattern shows which digit, `None` when the patterns can't come from a display.
+fn decode(patterns: &[Digit]) -> Option<Vec<Digit>> {
+    // Create a map of digits
+    let mut digits: Vec<Digit> = vec![BTreeSet::new(); 10];
+
+    // Find of size 2 (1)
+    digits[1] = patterns.iter().find(|p| p.len() == 2)?.clone();
+
+    // Find of size 3 (7)
+    digits[7] = patterns.iter().find(|p| p.len() == 3)?.clone();
+
+    // Find of size 4 (4)
+    digits[4] = patterns.iter().find(|p| p.len() == 4)?.clone();
+
+    // Find of size 7 (8)
+    digits[8] = patterns.iter().find(|p| p.len() == 7)?.clone();
+
+    // Now that we have 4 we can decode 9
+    digits[9] = patterns
+        .iter()
+        .filter(|p| p.len() == 6)
+        .find(|p| p.is_superset(&digits[4]))?
+        .clone();
+
+    // Now that we have 1 and 9 we can decode 0
+    digits[0] = patterns
+        .iter()
+        .filter(|p| p.len() == 6)
+        .find(|p| p.is_superset(&digits[1]) && **p != digits[9])?
+        .clone();
+
+    // Now that we have 0 and 9 we can decode 6
+    digits[6] = patterns
+        .iter()
+        .filter(|p| p.len() == 6)
+        .find(|p| **p != digits[0] && **p != digits[9])?
+        .clone();
+
+    // Now that we have 1 and 7 we can decode 3
+    digits[3] = patterns
+        .iter()
+        .filter(|p| p.len() == 5)
+        .find(|p| p.is_superset(&digits[1]) && p.is_superset(&digits[7]))?
+        .clone();
+
+    // Now that we have 6 we can decode 5 as it is the only subset of it
+    digits[5] = patterns
+        .iter()
+        .filter(|p| p.len() == 5)
+        .find(|p| p.is_subset(&digits[6]))?
+        .clone();
+
+    // Now that we have the rest we can decode 2
+    digits[2] = patterns
+        .iter()
+        .filter(|p| p.len() == 5)
+        .find(|p| **p != digits[3] && **p != digits[5])?
+        .clone();
+
+    // Every pattern has to show a different digit
+    if digits.iter().collect::<BTreeSet<_>>().len() != digits.len() {
+        return None;
+    }
+
+    Some(digits)
+}
+
+#[aoc_generator(day8)]
+pub fn input_generator(input: &str) -> Result<Vec<Entry>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            let (patterns, outputs) = line.split_once(" | ").ok_or_else(|| {
+                ParseError::unexpected(input, line, "patterns and outputs split by ` | `")
+            })?;
+
+            let digits = decode(&parse_digits(input, patterns, 10, "10 signal patterns")?)
+                .ok_or_else(|| {
+                    ParseError::unexpected(input, patterns, "the signal patterns of the ten digits")
+                })?;
+            let output_words = outputs;
+            let outputs = parse_digits(input, outputs, 4, "4 output digits")?;
+
+            if let Some(index) = outputs.iter().position(|output| !digits.contains(output)) {
+                return Err(ParseError::unexpected(
+                    input,
+                    output_words
+                        .split_whitespace()
+                        .nth(index)
+                        .unwrap_or(output_words),
+                    "one of the signal patterns",
+                ));
+            }
+
+            Ok(Entry {
+                patterns: digits,
+                outputs,
+            })
+        })
+        .collect()
+}
+
 /// Number segment usage:
 /// 0 uses 6 segments
 /// 1 uses 2 segments
@@ -12,163 +150,104 @@ use std::collections::BTreeSet;
 /// 8 uses 7 segments
 /// 9 uses 6 segments
 #[aoc(day8, part1)]
-pub fn solve_part1(input: &str) -> usize {
+pub fn solve_part1(input: &[Entry]) -> usize {
     input
-        .lines()
-        .map(|line| {
-            line.split(" | ")
-                .nth(1)
-                .unwrap()
-                .split(' ')
-                .map(|output| output.len())
-                .collect::<Vec<usize>>()
-        })
-        .flatten()
+        .iter()
+        .flat_map(|entry| entry.outputs.iter().map(|output| output.len()))
         .filter(|count| matches!(count, 2 | 3 | 4 | 7))
         .count()
 }
 
-fn get_vec_set_char(input: &str) -> Vec<BTreeSet<char>> {
-    input
-        .split_whitespace()
-        .map(|i| i.chars().collect())
-        .collect()
-}
-
 #[aoc(day8, part2)]
-pub fn solve_part2(input: &str) -> usize {
+pub fn solve_part2(input: &[Entry]) -> usize {
     input
-        .lines()
-        .map(|line| line.split_once(" | ").unwrap())
-        .map(|(pattern, output)| {
-            let patterns: Vec<BTreeSet<char>> = get_vec_set_char(pattern);
-            let outputs: Vec<BTreeSet<char>> = get_vec_set_char(output);
-
-            // Create a map of digits
-            let mut digits: Vec<BTreeSet<char>> = vec![BTreeSet::new(); 10];
-
-            // Find of size 2 (1)
-            if let Some(digit_1) = patterns.iter().find(|p| p.len() == 2) {
-                digits[1] = digit_1.clone()
-            }
-
-            // Find of size 3 (7)
-            if let Some(digit_7) = patterns.iter().find(|p| p.len() == 3) {
-                digits[7] = digit_7.clone()
-            }
-
-            // Find of size 4 (4)
-            if let Some(digit_4) = patterns.iter().find(|p| p.len() == 4) {
-                digits[4] = digit_4.clone()
-            }
-
-            // Find of size 7 (8)
-            if let Some(digit_8) = patterns.iter().find(|p| p.len() == 7) {
-                digits[8] = digit_8.clone()
-            }
-
-            // Now that we have 4 we can decode 9
-            if let Some(digit_9) = patterns
-                .iter()
-                .filter(|p| p.len() == 6)
-                .find(|p| p.is_superset(&digits[4]))
-            {
-                digits[9] = digit_9.clone()
-            }
-
-            // Now that we have 1 and 9 we can decode 0
-            if let Some(digit_0) = patterns
+        .iter()
+        .map(|Entry { patterns, outputs }| {
+            outputs
                 .iter()
-                .filter(|p| p.len() == 6)
-                .find(|p| p.is_superset(&digits[1]) && **p != digits[9])
-            {
-                digits[0] = digit_0.clone()
-            }
+                .filter_map(|output| patterns.iter().position(|pattern| pattern == output))
+                .fold(0, |acc, dig| acc * 10 + dig)
+        })
+        .sum()
+}
 
-            // Now that we have 0 and 9 we can decode 6
-            if let Some(digit_6) = patterns
-                .iter()
-                .filter(|p| p.len() == 6)
-                .find(|p| **p != digits[0] && **p != digits[9])
-            {
-                digits[6] = digit_6.clone()
-            }
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
 
-            // Now that we have 1 and 7 we can decode 3
-            if let Some(digit_3) = patterns
-                .iter()
-                .filter(|p| p.len() == 5)
-                .find(|p| p.is_superset(&digits[1]) && p.is_superset(&digits[7]))
-            {
-                digits[3] = digit_3.clone()
-            }
+    use super::*;
 
-            // Now that we have 6 we can decode 5 as it is the only subset of it
-            if let Some(digit_5) = patterns
-                .iter()
-                .filter(|p| p.len() == 5)
-                .find(|p| p.is_subset(&digits[6]))
-            {
-                digits[5] = digit_5.clone()
-            }
+    /// The segments of every digit on a display that is wired correctly.
+    const SEGMENTS: [&str; 10] = [
+        "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+    ];
 
-            // Now that we have the rest we can decode 2
-            if let Some(digit_2) = patterns
-                .iter()
-                .filter(|p| p.len() == 5)
-                .find(|p| **p != digits[3] && **p != digits[5])
-            {
-                digits[2] = digit_2.clone()
-            }
+    /// An entry of a display with its wires mixed up, and the order its patterns are listed in.
+    fn entry() -> impl Strategy<Value = (Entry, Vec<usize>)> {
+        let wires = Just("abcdefg".chars().collect::<Vec<_>>()).prop_shuffle();
+        let order = Just((0..10).collect::<Vec<_>>()).prop_shuffle();
 
-            outputs
+        (wires, order, prop::collection::vec(0..10usize, 4)).prop_map(|(wires, order, outputs)| {
+            let patterns = SEGMENTS
                 .iter()
-                .map(|output_value| {
-                    digits
-                        .iter()
-                        .enumerate()
-                        .find(|(_, value)| &output_value == value)
-                        .unwrap()
-                        .0
+                .map(|segments| {
+                    segments
+                        .chars()
+                        .map(|segment| wires[(segment as u8 - b'a') as usize])
+                        .collect::<Digit>()
                 })
-                .fold(0, |acc, dig| acc * 10 + dig as usize)
+                .collect::<Vec<_>>();
+            let outputs = outputs
+                .iter()
+                .map(|&digit| patterns[digit].clone())
+                .collect();
+
+            (Entry { patterns, outputs }, order)
         })
-        .sum()
-}
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    fn words<'a>(digits: impl Iterator<Item = &'a Digit>) -> String {
+        digits
+            .map(|digit| digit.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
 
-    #[test]
-    fn test_example_part1() {
-        let input = r#"be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
-edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
-fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
-fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
-aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
-fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
-dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
-bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
-egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
-gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce"#;
-
-        assert_eq!(26, solve_part1(input))
+    fn render(entry: &Entry, order: &[usize]) -> String {
+        format!(
+            "{} | {}",
+            words(order.iter().map(|&digit| &entry.patterns[digit])),
+            words(entry.outputs.iter())
+        )
     }
 
     #[test]
-    fn test_example_part2() {
-        let input = r#"be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
-edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
-fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
-fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
-aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
-fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
-dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
-bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
-egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
-gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce"#;
-
-        assert_eq!(61229, solve_part2(input))
+    fn test_decode() {
+        let digits = "abcefg cf acdeg acdfg bcdf abdfg abdefg acf abcdefg abcdfg";
+
+        assert_eq!(
+            1151,
+            solve_part2(&input_generator(&format!("{} | cf cf abdfg cf", digits)).unwrap())
+        );
+        assert_eq!(
+            "line 1, column 1: expected the signal patterns of the ten digits, found `ab ab ab ab \
+             ab ab ab ab ab ab`",
+            input_generator("ab ab ab ab ab ab ab ab ab ab | ab ab ab ab")
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(
+            "line 1, column 65: expected one of the signal patterns, found `abc`",
+            input_generator(&format!("{} | cf abc cf cf", digits))
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_entry_round_trip((entry, order) in entry()) {
+            prop_assert_eq!(Ok(vec![entry.clone()]), input_generator(&render(&entry, &order)));
+        }
     }
 }
@@ -1,92 +1,125 @@
-use std::collections::{BTreeMap, BTreeSet, VecDeque};
-use std::num::ParseIntError;
+use std::collections::VecDeque;
 use std::str::FromStr;
 
-#[derive(Clone)]
-pub struct Cave(BTreeMap<(i32, i32), u32>, BTreeSet<(i32, i32)>);
+use crate::error::ParseError;
+use crate::grid::{Grid, Position};
+use crate::visualize::{Color, Frame, Visualize};
+
+/// The colours basins are filled with, reused when there are more basins than colours.
+const BASIN_COLORS: [Color; 4] = [Color::BLUE, Color::GREEN, Color::YELLOW, Color::RED];
+
+pub struct Cave(Grid<u32>);
 
 impl Cave {
-    pub fn height_at(&self, (x, y): (i32, i32)) -> u32 {
-        *self.0.get(&(x, y)).unwrap_or(&u32::MAX)
+    pub fn height_at(&self, position: Position) -> u32 {
+        self.0[position]
     }
 
-    pub fn find_low_points(&self) -> Vec<(&(i32, i32), &u32)> {
+    pub fn find_low_points(&self) -> Vec<(Position, u32)> {
         self.0
             .iter()
-            .filter(|((x, y), current_depth)| {
-                // Get all neighbours
-                let north = self.height_at((*x, *y - 1));
-                let east = self.height_at((*x + 1, *y));
-                let south = self.height_at((*x, y + 1));
-                let west = self.height_at((*x - 1, *y));
-
-                north > **current_depth
-                    && east > **current_depth
-                    && south > **current_depth
-                    && west > **current_depth
+            .filter(|(position, current_depth)| {
+                self.0
+                    .neighbours4(*position)
+                    .all(|neighbour| self.height_at(neighbour) > **current_depth)
             })
+            .map(|(position, depth)| (position, *depth))
             .collect::<Vec<_>>()
     }
 
-    pub fn explore_basin(&mut self, (x, y): (i32, i32)) -> usize {
-        let mut basin = BTreeSet::new();
+    pub fn explore_basin(&self, position: Position) -> usize {
+        let mut basin = Grid::filled(self.0.width(), self.0.height(), false);
+        let mut size = 0;
         let mut queue = VecDeque::new();
-        queue.push_back((x, y));
+        queue.push_back(position);
 
-        while let Some((x, y)) = queue.pop_front() {
+        while let Some(position) = queue.pop_front() {
             // Ignore if we already processed this position
-            if self.1.contains(&(x, y)) {
+            if basin[position] {
                 continue;
             }
 
             // Search to find where there is height 9
-            if (0..9).contains(&self.height_at((x, y))) && basin.insert((x, y)) {
-                queue.push_back((x, y - 1));
-                queue.push_back((x - 1, y));
-                queue.push_back((x + 1, y));
-                queue.push_back((x, y + 1));
+            if self.height_at(position) < 9 {
+                basin[position] = true;
+                size += 1;
+
+                queue.extend(self.0.neighbours4(position));
             }
         }
 
-        basin.len()
+        size
+    }
+}
+
+impl Visualize for Cave {
+    /// The heights in gray, with every basin flooding from its low point one cell further each
+    /// frame.
+    fn frames(&self) -> Vec<Frame> {
+        let mut frame = self.0.map(|&height| Color::gray(height as u8 * 28));
+        let mut frames = vec![frame.clone()];
+        let mut visited = Grid::filled(self.0.width(), self.0.height(), false);
+
+        let mut wave = self
+            .find_low_points()
+            .into_iter()
+            .enumerate()
+            .map(|(basin, (position, _))| (position, basin))
+            .collect::<Vec<_>>();
+
+        for &(position, _) in &wave {
+            visited[position] = true;
+        }
+
+        while !wave.is_empty() {
+            let mut next = vec![];
+
+            for (position, basin) in wave {
+                frame[position] = BASIN_COLORS[basin % BASIN_COLORS.len()];
+
+                for neighbour in self.0.neighbours4(position) {
+                    if !visited[neighbour] && self.height_at(neighbour) < 9 {
+                        visited[neighbour] = true;
+                        next.push((neighbour, basin));
+                    }
+                }
+            }
+
+            frames.push(frame.clone());
+            wave = next;
+        }
+
+        frames
     }
 }
 
 impl FromStr for Cave {
-    type Err = ParseIntError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let cave = (s.lines().enumerate())
-            .flat_map(|(y, line)| {
-                line.chars()
-                    .enumerate()
-                    .map(move |(x, height)| ((x as i32, y as i32), height.to_digit(10).unwrap()))
-            })
-            .collect();
-
-        Ok(Self(cave, BTreeSet::new()))
+        Ok(Self(Grid::from_digits(s)?))
     }
 }
 
-#[aoc(day9, part1)]
-pub fn solve_part1(input: &str) -> u32 {
-    let cave: Cave = input.parse().unwrap();
+#[aoc_generator(day9)]
+pub fn input_generator(input: &str) -> Result<Cave, ParseError> {
+    input.parse()
+}
 
+#[aoc(day9, part1)]
+pub fn solve_part1(cave: &Cave) -> u32 {
     cave.find_low_points()
         .iter()
         .fold(0, |risk, (_, depth)| risk + (*depth + 1))
 }
 
 #[aoc(day9, part2)]
-pub fn solve_part2(input: &str) -> u32 {
-    let mut cave: Cave = input.parse().unwrap();
-    let clone = cave.clone();
-
+pub fn solve_part2(cave: &Cave) -> u32 {
     // Find basins
-    let mut basins = clone
+    let mut basins = cave
         .find_low_points()
         .iter()
-        .map(|(position, _)| cave.explore_basin(**position) as u32)
+        .map(|(position, _)| cave.explore_basin(*position) as u32)
         .collect::<Vec<_>>();
 
     basins.sort_unstable();
@@ -98,24 +131,20 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_example_part1() {
-        let input = r#"2199943210
-3987894921
-9856789892
-8767896789
-9899965678"#;
-
-        assert_eq!(15, solve_part1(&input))
-    }
-
-    #[test]
-    fn test_example_part2() {
-        let input = r#"2199943210
-3987894921
-9856789892
-8767896789
-9899965678"#;
-
-        assert_eq!(1134, solve_part2(&input))
+    fn test_frames() {
+        let cave =
+            input_generator(include_str!("../examples/2021/day9/example.txt").trim_end()).unwrap();
+        let frames = cave.frames();
+        let flooded = frames
+            .last()
+            .unwrap()
+            .values()
+            .filter(|color| BASIN_COLORS.contains(color))
+            .count();
+
+        assert!(frames[0]
+            .values()
+            .all(|color| !BASIN_COLORS.contains(color)));
+        assert_eq!(3 + 9 + 14 + 9, flooded);
     }
 }
@@ -1,22 +1,19 @@
+use crate::error::{parse_number, ParseError};
+
 #[aoc_generator(day0)]
-pub fn input_generator(input: &str) -> Vec<usize> {
+pub fn input_generator(input: &str) -> Result<Vec<usize>, ParseError> {
     input
         .lines()
-        .map(|line| line.parse::<usize>().unwrap())
+        .map(|line| parse_number(input, line))
         .collect()
 }
 
 #[aoc(day0, part1)]
-pub fn solve_part1(input: &[usize]) -> usize {
+pub fn solve_part1(_input: &[usize]) -> usize {
     0
 }
 
 #[aoc(day0, part2)]
-pub fn solve_part2(input: &[usize]) -> usize {
+pub fn solve_part2(_input: &[usize]) -> usize {
     0
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-}
\ No newline at end of file
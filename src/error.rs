@@ -0,0 +1,192 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A 1-based line and column in the puzzle input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Location {
+    /// Find where `token` starts in `source`. The token must be a slice of the source, anything else
+    /// is reported at the end of the source.
+    pub fn of(source: &str, token: &str) -> Self {
+        let start = source.as_ptr() as usize;
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|offset| *offset <= source.len())
+            .unwrap_or(source.len());
+
+        let before = &source[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before.len() - before.rfind('\n').map_or(0, |newline| newline + 1) + 1;
+
+        Self { line, column }
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// An error produced when the puzzle input does not have the expected shape.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A token that should have been a number.
+    InvalidNumber { location: Location, snippet: String },
+    /// A token that is not what the puzzle format allows at that point.
+    UnexpectedToken {
+        location: Location,
+        snippet: String,
+        expected: &'static str,
+    },
+    /// The input stopped while more was expected.
+    UnexpectedEnd {
+        location: Location,
+        expected: &'static str,
+    },
+}
+
+impl ParseError {
+    pub fn invalid_number(source: &str, token: &str) -> Self {
+        Self::InvalidNumber {
+            location: Location::of(source, token),
+            snippet: token.to_string(),
+        }
+    }
+
+    pub fn unexpected(source: &str, token: &str, expected: &'static str) -> Self {
+        Self::UnexpectedToken {
+            location: Location::of(source, token),
+            snippet: token.to_string(),
+            expected,
+        }
+    }
+
+    pub fn unexpected_end(source: &str, expected: &'static str) -> Self {
+        Self::UnexpectedEnd {
+            location: Location::of(source, &source[source.len()..]),
+            expected,
+        }
+    }
+
+    pub fn location(&self) -> Location {
+        match self {
+            Self::InvalidNumber { location, .. }
+            | Self::UnexpectedToken { location, .. }
+            | Self::UnexpectedEnd { location, .. } => *location,
+        }
+    }
+
+    /// Move an error that was produced while parsing `token` on its own so that its location is
+    /// relative to `source` instead, which is how line based `FromStr` impls report the real line.
+    pub fn within(mut self, source: &str, token: &str) -> Self {
+        let base = Location::of(source, token);
+
+        let location = match &mut self {
+            Self::InvalidNumber { location, .. }
+            | Self::UnexpectedToken { location, .. }
+            | Self::UnexpectedEnd { location, .. } => location,
+        };
+
+        if location.line == 1 {
+            location.column += base.column - 1;
+        }
+        location.line += base.line - 1;
+
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidNumber { location, snippet } => {
+                write!(f, "{}: expected a number, found `{}`", location, snippet)
+            }
+            Self::UnexpectedToken {
+                location,
+                snippet,
+                expected,
+            } if snippet.is_empty() => {
+                write!(
+                    f,
+                    "{}: expected {}, found an empty line",
+                    location, expected
+                )
+            }
+            Self::UnexpectedToken {
+                location,
+                snippet,
+                expected,
+            } => write!(
+                f,
+                "{}: expected {}, found `{}`",
+                location, expected, snippet
+            ),
+            Self::UnexpectedEnd { location, expected } => {
+                write!(f, "{}: expected {}, found end of input", location, expected)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parse `token` as a number, reporting its location in `source` when it is not one.
+pub fn parse_number<T: FromStr>(source: &str, token: &str) -> Result<T, ParseError> {
+    token
+        .parse::<T>()
+        .map_err(|_| ParseError::invalid_number(source, token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_location_of_token() {
+        let source = "1,2\n3,x4\n";
+        let token = &source[6..8];
+
+        assert_eq!(Location { line: 2, column: 3 }, Location::of(source, token));
+    }
+
+    #[test]
+    fn test_location_of_foreign_token_is_end() {
+        let source = "ab\ncd";
+
+        assert_eq!(Location { line: 2, column: 3 }, Location::of(source, "x"));
+    }
+
+    #[test]
+    fn test_parse_number_error() {
+        let source = "12\n1a";
+
+        assert_eq!(Ok(12), parse_number::<usize>(source, &source[..2]));
+        assert_eq!(
+            "line 2, column 1: expected a number, found `1a`",
+            parse_number::<usize>(source, &source[3..])
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_within_shifts_location() {
+        let source = "forward 5\nup x";
+        let line = &source[10..];
+
+        let error = parse_number::<usize>(line, &line[3..]).unwrap_err();
+
+        assert_eq!(Location { line: 1, column: 4 }, error.location());
+        assert_eq!(
+            Location { line: 2, column: 4 },
+            error.within(source, line).location()
+        );
+    }
+}
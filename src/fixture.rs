@@ -0,0 +1,163 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::manifest::{Manifest, ManifestError, Verification};
+use crate::solver::Registry;
+
+/// Where the examples from the puzzle descriptions are kept, in a `dayN` directory per day.
+pub const DEFAULT_FIXTURES: &str = "examples/2021";
+
+/// An error produced when a fixture or its answers cannot be read.
+#[derive(Debug)]
+pub struct FixtureError {
+    pub path: PathBuf,
+    pub error: ManifestError,
+}
+
+impl Display for FixtureError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.error)
+    }
+}
+
+impl Error for FixtureError {}
+
+/// An example input with its expected answers, read from `dayN/NAME.txt` and `dayN/NAME.toml`.
+///
+/// The answers file only has `partN` keys, a part without an answer is not checked.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Fixture {
+    pub day: u32,
+    pub name: String,
+    pub input: String,
+    pub answers: Manifest,
+}
+
+fn read(path: &Path) -> Result<String, FixtureError> {
+    fs::read_to_string(path).map_err(|e| FixtureError {
+        path: path.to_path_buf(),
+        error: ManifestError::Io(e),
+    })
+}
+
+fn read_dir(path: &Path) -> Result<Vec<PathBuf>, FixtureError> {
+    let mut paths = fs::read_dir(path)
+        .and_then(|entries| {
+            entries
+                .map(|entry| Ok(entry?.path()))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(|e| FixtureError {
+            path: path.to_path_buf(),
+            error: ManifestError::Io(e),
+        })?;
+    paths.sort();
+
+    Ok(paths)
+}
+
+impl Fixture {
+    pub fn load(day: u32, input: &Path) -> Result<Self, FixtureError> {
+        let answers = input.with_extension("toml");
+        let name = input
+            .file_stem()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        Ok(Self {
+            day,
+            name,
+            input: read(input)?,
+            answers: Manifest::parse_day(day, &read(&answers)?).map_err(|error| FixtureError {
+                path: answers,
+                error,
+            })?,
+        })
+    }
+
+    /// Run the parts with an expected answer, a part without a solver counts as a failure.
+    pub fn verify(&self, registry: &Registry) -> Vec<Verification> {
+        self.answers
+            .parts(self.day)
+            .into_iter()
+            .map(|part| Verification {
+                day: self.day,
+                part,
+                outcome: self.answers.outcome(
+                    self.day,
+                    part,
+                    registry.run(self.day, part, &self.input),
+                ),
+            })
+            .collect()
+    }
+}
+
+/// Find every `dayN/*.txt` fixture below `dir`, ordered by day and name.
+pub fn discover(dir: &Path) -> Result<Vec<Fixture>, FixtureError> {
+    let mut fixtures = vec![];
+
+    for day_dir in read_dir(dir)? {
+        let day = match day_dir
+            .file_name()
+            .and_then(|name| name.to_str()?.strip_prefix("day")?.parse().ok())
+        {
+            Some(day) if day_dir.is_dir() => day,
+            _ => continue,
+        };
+
+        for input in read_dir(&day_dir)? {
+            if input
+                .extension()
+                .is_some_and(|extension| extension == "txt")
+            {
+                fixtures.push(Fixture::load(day, &input)?);
+            }
+        }
+    }
+
+    fixtures.sort_by(|a, b| (a.day, &a.name).cmp(&(b.day, &b.name)));
+
+    Ok(fixtures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::Outcome;
+    use crate::registry;
+
+    #[test]
+    fn test_discover() {
+        let fixtures = discover(Path::new(DEFAULT_FIXTURES)).unwrap();
+        let day12 = fixtures
+            .iter()
+            .filter(|fixture| fixture.day == 12)
+            .map(|fixture| fixture.name.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec!["large", "medium", "small"], day12);
+        assert!(fixtures.windows(2).all(|pair| pair[0].day <= pair[1].day));
+    }
+
+    #[test]
+    fn test_verify_without_solver() {
+        let fixture = Fixture {
+            day: 7,
+            name: "example".to_string(),
+            input: "16,1,2,0,4,2,7,1,2,14\n".to_string(),
+            answers: Manifest::parse_day(7, "part1 = 37\npart3 = 1").unwrap(),
+        };
+
+        let outcomes = fixture
+            .verify(&registry())
+            .into_iter()
+            .map(|verification| verification.outcome)
+            .collect::<Vec<_>>();
+
+        assert_eq!(Outcome::Correct, outcomes[0]);
+        assert!(matches!(outcomes[1], Outcome::Failed(_)));
+    }
+}
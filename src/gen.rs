@@ -0,0 +1,961 @@
+use std::collections::BTreeSet;
+use std::ops::RangeInclusive;
+
+use crate::day16::{Contents, Operator, Packet};
+use crate::day19::{Point, Rotation};
+use crate::{day11, day19, day23, day25};
+
+/// A small seeded pseudo-random number generator (SplitMix64), so the same seed always generates
+/// the same input.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number below `n`, which has to be positive.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// A number in the range, which must not be empty.
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let span = (range.end() - range.start()) as u64 + 1;
+
+        range.start() + (self.next_u64() % span) as i64
+    }
+
+    pub fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// Generate an input for the day, or `None` when there is no generator for it. What `size`
+/// counts differs per day, see the generators themselves.
+pub fn generate(day: u32, size: usize, seed: u64) -> Option<String> {
+    let rng = &mut Rng::new(seed);
+    let size = size.max(1);
+
+    let input = match day {
+        1 => day1(rng, size),
+        2 => day2(rng, size),
+        3 => day3(rng, size),
+        4 => day4(rng, size),
+        5 => day5(rng, size),
+        6 => day6(rng, size),
+        7 => day7(rng, size),
+        8 => day8(rng, size),
+        9 => day9(rng, size),
+        10 => day10(rng, size),
+        11 => day11(rng, size),
+        12 => day12(rng, size),
+        13 => day13(rng, size),
+        14 => day14(rng, size),
+        15 => day15(rng, size),
+        16 => day16(rng, size),
+        17 => day17(rng, size),
+        18 => day18(rng, size),
+        19 => day19(rng, size),
+        20 => day20(rng, size),
+        21 => day21(rng, size),
+        22 => day22(rng, size),
+        23 => day23(rng, size),
+        24 => day24(rng, size),
+        25 => day25(rng, size),
+        _ => return None,
+    };
+
+    Some(input)
+}
+
+/// The size that roughly matches the real puzzle input of the day.
+pub fn default_size(day: u32) -> usize {
+    match day {
+        1 => 2000,
+        2 | 3 | 7 => 1000,
+        5 => 500,
+        6 => 300,
+        8 => 200,
+        11 | 12 => 10,
+        13 => 800,
+        14 => 20,
+        16 => 60,
+        19 => 30,
+        22 => 420,
+        24 => 14,
+        25 => 140,
+        _ => 100,
+    }
+}
+
+fn lines(lines: impl IntoIterator<Item = String>) -> String {
+    lines.into_iter().collect::<Vec<_>>().join("\n")
+}
+
+fn digit_grid(rng: &mut Rng, width: usize, height: usize, digits: RangeInclusive<i64>) -> String {
+    lines((0..height).map(|_| {
+        (0..width)
+            .map(|_| rng.range(digits.clone()).to_string())
+            .collect()
+    }))
+}
+
+/// `size` sonar depths that drift downwards.
+pub fn day1(rng: &mut Rng, size: usize) -> String {
+    let mut depth = rng.range(100..=200);
+
+    lines((0..size).map(|_| {
+        depth = (depth + rng.range(-10..=20)).max(0);
+        depth.to_string()
+    }))
+}
+
+/// `size` submarine commands that never take it above the surface.
+pub fn day2(rng: &mut Rng, size: usize) -> String {
+    let mut aim = 0;
+
+    lines((0..size).map(|_| {
+        let amount = rng.range(1..=9);
+        let direction = match *rng.choose(&["forward", "down", "up"]) {
+            // Going up by more than the aim would give a negative depth
+            "up" if amount > aim => "down",
+            direction => direction,
+        };
+
+        match direction {
+            "down" => aim += amount,
+            "up" => aim -= amount,
+            _ => {}
+        }
+
+        format!("{} {}", direction, amount)
+    }))
+}
+
+/// `size` distinct binary numbers, at least 12 bits wide, from which both ratings can be found.
+pub fn day3(rng: &mut Rng, size: usize) -> String {
+    let width = (usize::BITS - (size - 1).leading_zeros()).max(12) as usize;
+    let mut numbers = BTreeSet::new();
+
+    // The oxygen generator rating is only found when no two numbers are the same
+    while numbers.len() < size {
+        numbers.insert(rng.next_u64() & ((1 << width) - 1));
+    }
+
+    let mut numbers = numbers.into_iter().collect::<Vec<_>>();
+
+    // The CO2 scrubber rating keeps the least common bit, so none are left when every remaining
+    // number has the same bit. Flipping it in one of them keeps the numbers distinct, as the
+    // remaining numbers are all those starting with the bits before it.
+    let mut remaining = (0..numbers.len()).collect::<Vec<_>>();
+    for bit in (0..width).rev() {
+        if remaining.len() == 1 {
+            break;
+        }
+
+        let has_one = |numbers: &[u64], &index: &usize| numbers[index] >> bit & 1 == 1;
+        let ones = remaining
+            .iter()
+            .filter(|index| has_one(&numbers, index))
+            .count();
+        if ones == 0 || ones == remaining.len() {
+            numbers[remaining[0]] ^= 1 << bit;
+        }
+
+        let ones = remaining
+            .iter()
+            .filter(|index| has_one(&numbers, index))
+            .count();
+        let keep_ones = ones * 2 < remaining.len();
+        remaining.retain(|index| has_one(&numbers, index) == keep_ones);
+    }
+
+    rng.shuffle(&mut numbers);
+
+    lines(
+        numbers
+            .iter()
+            .map(|number| format!("{:0width$b}", number, width = width)),
+    )
+}
+
+/// Draw every number from 0 to 99 for `size` boards, so every board wins eventually.
+pub fn day4(rng: &mut Rng, size: usize) -> String {
+    let mut numbers = (0..100).collect::<Vec<_>>();
+    rng.shuffle(&mut numbers);
+
+    let drawn = numbers
+        .iter()
+        .map(|number| number.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let boards = (0..size).map(|_| {
+        rng.shuffle(&mut numbers);
+
+        lines(numbers[..25].chunks(5).map(|row| {
+            row.iter()
+                .map(|number| format!("{:>2}", number))
+                .collect::<Vec<_>>()
+                .join(" ")
+        }))
+    });
+
+    format!("{}\n\n{}", drawn, boards.collect::<Vec<_>>().join("\n\n"))
+}
+
+/// `size` horizontal, vertical and diagonal lines of vents between 0 and 999.
+pub fn day5(rng: &mut Rng, size: usize) -> String {
+    const LIMIT: i64 = 999;
+    const DIRECTIONS: [(i64, i64); 8] = [
+        (1, 0),
+        (-1, 0),
+        (0, 1),
+        (0, -1),
+        (1, 1),
+        (1, -1),
+        (-1, 1),
+        (-1, -1),
+    ];
+
+    lines((0..size).map(|_| {
+        let (x, y) = (rng.range(0..=LIMIT), rng.range(0..=LIMIT));
+        let &(dx, dy) = rng.choose(&DIRECTIONS);
+
+        // Keep the end of the line on the map
+        let room = |position: i64, delta: i64| match delta {
+            1 => LIMIT - position,
+            -1 => position,
+            _ => LIMIT,
+        };
+        let length = rng.range(0..=room(x, dx).min(room(y, dy)).min(300));
+
+        format!("{},{} -> {},{}", x, y, x + dx * length, y + dy * length)
+    }))
+}
+
+/// `size` lanternfish timers.
+pub fn day6(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| rng.range(1..=5).to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// `size` crab positions.
+pub fn day7(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| rng.range(0..=1999).to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// The segments of the digits 0 to 9 on a correctly wired display.
+const SEGMENTS: [&str; 10] = [
+    "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+];
+
+fn scramble(rng: &mut Rng, wiring: &[char], digit: &str) -> String {
+    let mut segments = digit
+        .bytes()
+        .map(|segment| wiring[(segment - b'a') as usize])
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut segments);
+
+    segments.into_iter().collect()
+}
+
+/// `size` displays, each with its own random wiring.
+pub fn day8(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        let mut wiring = ('a'..='g').collect::<Vec<_>>();
+        rng.shuffle(&mut wiring);
+
+        let mut digits = (0..10).collect::<Vec<_>>();
+        rng.shuffle(&mut digits);
+
+        let patterns = digits
+            .iter()
+            .map(|&digit| scramble(rng, &wiring, SEGMENTS[digit]))
+            .collect::<Vec<_>>();
+        let outputs = (0..4)
+            .map(|_| {
+                let digit = rng.below(10);
+                scramble(rng, &wiring, SEGMENTS[digit])
+            })
+            .collect::<Vec<_>>();
+
+        format!("{} | {}", patterns.join(" "), outputs.join(" "))
+    }))
+}
+
+/// A `size` by `size` height map.
+pub fn day9(rng: &mut Rng, size: usize) -> String {
+    digit_grid(rng, size, size, 0..=9)
+}
+
+const BRACKETS: [(char, char); 4] = [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
+
+fn navigation_line(rng: &mut Rng, corrupted: bool) -> String {
+    let mut line = String::new();
+    let mut stack = vec![];
+
+    for _ in 0..rng.range(10..=90) {
+        match stack.pop() {
+            Some(close) if rng.chance(0.4) => line.push(close),
+            top => {
+                stack.extend(top);
+
+                let &(open, close) = rng.choose(&BRACKETS);
+                line.push(open);
+                stack.push(close);
+            }
+        }
+    }
+
+    if stack.is_empty() {
+        let &(open, close) = rng.choose(&BRACKETS);
+        line.push(open);
+        stack.push(close);
+    }
+
+    if corrupted {
+        let expected = stack[stack.len() - 1];
+        let wrong = BRACKETS
+            .iter()
+            .map(|&(_, close)| close)
+            .filter(|&close| close != expected)
+            .collect::<Vec<_>>();
+        line.push(*rng.choose(&wrong));
+
+        // Whatever follows the first illegal character doesn't matter
+        for _ in 0..rng.range(0..=20) {
+            line.push(rng.choose(&BRACKETS).0);
+        }
+    }
+
+    line
+}
+
+/// `size` navigation lines, about half of them corrupted and the rest incomplete.
+pub fn day10(rng: &mut Rng, size: usize) -> String {
+    // The middle score is taken from the incomplete lines, so there has to be an odd number of them
+    let incomplete = (size / 2) | 1;
+
+    let mut navigation = (0..size.max(incomplete))
+        .map(|index| navigation_line(rng, index >= incomplete))
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut navigation);
+
+    lines(navigation)
+}
+
+/// How many steps a generated simulation may take before it has to settle.
+const STEP_LIMIT: usize = 1000;
+
+/// A `size` by `size` grid of octopus energy levels that all flash at once within
+/// [`STEP_LIMIT`] steps. Large grids hardly ever do, so this only works for small sizes.
+pub fn day11(rng: &mut Rng, size: usize) -> String {
+    // Not every grid ends up flashing all at once, so keep trying until one does in time
+    loop {
+        let input = digit_grid(rng, size, size, 0..=9);
+        let mut cave = day11::input_generator(&input).expect("a grid of digits is a valid cave");
+
+        if (0..STEP_LIMIT).any(|_| day11::run_step(&mut cave) == size * size) {
+            return input;
+        }
+    }
+}
+
+fn cave_name(rng: &mut Rng, taken: &mut BTreeSet<String>, big: bool) -> String {
+    loop {
+        let name = (0..2)
+            .map(|_| (b'a' + rng.below(26) as u8) as char)
+            .collect::<String>();
+        let name = if big { name.to_uppercase() } else { name };
+
+        if taken.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+/// A cave system with `size` small caves and a big cave for every four of them. Big caves are
+/// never connected to each other, which would allow paths that never end.
+pub fn day12(rng: &mut Rng, size: usize) -> String {
+    let mut taken = BTreeSet::new();
+    let small = (0..size)
+        .map(|_| cave_name(rng, &mut taken, false))
+        .collect::<Vec<_>>();
+    let big = (0..size.div_ceil(4))
+        .map(|_| cave_name(rng, &mut taken, true))
+        .collect::<Vec<_>>();
+    let caves = small
+        .iter()
+        .chain(&big)
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+
+    let mut connections = BTreeSet::new();
+    let mut connect = |a: &str, b: &str| {
+        if a != b {
+            connections.insert(if a < b {
+                (a.to_string(), b.to_string())
+            } else {
+                (b.to_string(), a.to_string())
+            });
+        }
+    };
+
+    // Every small cave hangs off an earlier one, so the start and the end are always connected
+    for (index, cave) in small.iter().enumerate().skip(1) {
+        connect(cave, &small[rng.below(index)]);
+
+        if rng.chance(0.3) {
+            connect(cave, rng.choose::<&str>(&caves));
+        }
+    }
+
+    for cave in &big {
+        for _ in 0..rng.range(1..=2) {
+            connect(cave, &small[rng.below(small.len())]);
+        }
+    }
+
+    for end in ["start", "end"].iter() {
+        for _ in 0..rng.range(1..=2) {
+            connect(end, rng.choose::<&str>(&caves));
+        }
+    }
+
+    let mut connections = connections
+        .into_iter()
+        .map(|(a, b)| format!("{}-{}", a, b))
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut connections);
+
+    lines(connections)
+}
+
+/// About `size` dots, folded up into a 40 by 6 code like the real puzzle.
+pub fn day13(rng: &mut Rng, size: usize) -> String {
+    let mut axes = [vec![true; 5], vec![false; 7]].concat();
+    rng.shuffle(&mut axes);
+
+    // Unfold from the code outwards, every fold is along the middle line of the paper
+    let (mut width, mut height) = (40, 6);
+    let mut folds = vec![];
+
+    for &vertical in axes.iter().rev() {
+        if vertical {
+            folds.push((true, width));
+            width = 2 * width + 1;
+        } else {
+            folds.push((false, height));
+            height = 2 * height + 1;
+        }
+    }
+
+    let mut dots = BTreeSet::new();
+
+    for _ in 0..size {
+        let (mut x, mut y) = (rng.below(40), rng.below(6));
+
+        for &(vertical, line) in &folds {
+            if rng.chance(0.5) {
+                if vertical {
+                    x = 2 * line - x;
+                } else {
+                    y = 2 * line - y;
+                }
+            }
+        }
+
+        dots.insert((x, y));
+    }
+
+    let dots = lines(dots.iter().map(|(x, y)| format!("{},{}", x, y)));
+    let folds = lines(folds.iter().rev().map(|&(vertical, line)| {
+        format!("fold along {}={}", if vertical { 'x' } else { 'y' }, line)
+    }));
+
+    format!("{}\n\n{}", dots, folds)
+}
+
+/// A polymer template of `size` elements, with a rule for every pair of ten elements.
+pub fn day14(rng: &mut Rng, size: usize) -> String {
+    let mut elements = ('A'..='Z').collect::<Vec<_>>();
+    rng.shuffle(&mut elements);
+    elements.truncate(10);
+
+    let template = (0..size)
+        .map(|_| *rng.choose(&elements))
+        .collect::<String>();
+    let rules = lines(
+        elements
+            .iter()
+            .flat_map(|&a| elements.iter().map(move |&b| (a, b)).collect::<Vec<_>>())
+            .map(|(a, b)| format!("{}{} -> {}", a, b, rng.choose(&elements))),
+    );
+
+    format!("{}\n\n{}", template, rules)
+}
+
+/// A `size` by `size` map of risk levels.
+pub fn day15(rng: &mut Rng, size: usize) -> String {
+    digit_grid(rng, size, size, 1..=9)
+}
+
+fn random_packet(rng: &mut Rng, budget: &mut usize, depth: usize) -> Packet {
+    let version = rng.below(8) as u8;
+    *budget = budget.saturating_sub(1);
+
+    if *budget == 0 || depth >= 6 || rng.chance(0.3) {
+        return Packet::literal(version, rng.range(0..=1 << 20) as u64);
+    }
+
+    const OPERATORS: [Operator; 7] = [
+        Operator::Sum,
+        Operator::Product,
+        Operator::Minimum,
+        Operator::Maximum,
+        Operator::GreaterThan,
+        Operator::LessThan,
+        Operator::EqualTo,
+    ];
+
+    let operator = *rng.choose(&OPERATORS);
+
+    let packets = match operator {
+        // Products only multiply a few small literals, so the value can't overflow
+        Operator::Product => (0..rng.range(1..=3))
+            .map(|_| Packet::literal(rng.below(8) as u8, rng.range(0..=255) as u64))
+            .collect(),
+        Operator::GreaterThan | Operator::LessThan | Operator::EqualTo => (0..2)
+            .map(|_| random_packet(rng, budget, depth + 1))
+            .collect(),
+        _ => (0..rng.range(1..=4))
+            .map(|_| random_packet(rng, budget, depth + 1))
+            .collect(),
+    };
+
+    Packet::operator(version, operator, packets)
+}
+
+fn push_bits(bits: &mut Vec<bool>, value: u64, count: usize) {
+    bits.extend((0..count).rev().map(|bit| value >> bit & 1 == 1));
+}
+
+/// Encode the packet, picking either length type for every operator.
+fn encode(rng: &mut Rng, packet: &Packet, bits: &mut Vec<bool>) {
+    push_bits(bits, packet.version as u64, 3);
+
+    match &packet.contents {
+        Contents::Literal(value) => {
+            push_bits(bits, 4, 3);
+
+            let groups = ((u64::BITS - value.leading_zeros()) as usize)
+                .div_ceil(4)
+                .max(1);
+            for group in (0..groups).rev() {
+                push_bits(bits, (group > 0) as u64, 1);
+                push_bits(bits, value >> (4 * group) & 0xf, 4);
+            }
+        }
+        Contents::Operator { operator, packets } => {
+            push_bits(bits, operator.type_id(), 3);
+
+            let mut contents = vec![];
+            for packet in packets {
+                encode(rng, packet, &mut contents);
+            }
+
+            if rng.chance(0.5) && contents.len() < 1 << 15 {
+                push_bits(bits, 0, 1);
+                push_bits(bits, contents.len() as u64, 15);
+            } else {
+                push_bits(bits, 1, 1);
+                push_bits(bits, packets.len() as u64, 11);
+            }
+
+            bits.extend(contents);
+        }
+    }
+}
+
+/// A transmission of about `size` packets.
+pub fn day16(rng: &mut Rng, size: usize) -> String {
+    let packet = random_packet(rng, &mut size.clone(), 0);
+    let mut bits = vec![];
+    encode(rng, &packet, &mut bits);
+
+    bits.chunks(4)
+        .map(|nibble| {
+            let value = (0..4).fold(0, |value, bit| {
+                value << 1 | nibble.get(bit).copied().unwrap_or(false) as u32
+            });
+            std::char::from_digit(value, 16)
+                .expect("a nibble is a hexadecimal digit")
+                .to_ascii_uppercase()
+        })
+        .collect()
+}
+
+/// A target area below and to the right of the launcher, around `size` steps away.
+pub fn day17(rng: &mut Rng, size: usize) -> String {
+    let size = size as i64;
+    let x_min = rng.range(10..=size.max(20));
+    let x_max = x_min + rng.range(5..=30);
+    let y_max = -rng.range(5..=size / 2 + 10);
+    let y_min = y_max - rng.range(5..=30);
+
+    format!(
+        "target area: x={}..{}, y={}..{}",
+        x_min, x_max, y_min, y_max
+    )
+}
+
+/// A reduced snailfish pair, nested `depth` pairs deep.
+fn snailfish(rng: &mut Rng, depth: usize) -> String {
+    let element = |rng: &mut Rng| {
+        if depth == 4 || rng.chance(0.4) {
+            rng.range(0..=9).to_string()
+        } else {
+            snailfish(rng, depth + 1)
+        }
+    };
+
+    let left = element(rng);
+    format!("[{},{}]", left, element(rng))
+}
+
+/// `size` snailfish numbers.
+pub fn day18(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| snailfish(rng, 1)))
+}
+
+/// How far a scanner can see along every axis.
+const SCANNER_RANGE: i64 = day19::SCANNER_RANGE as i64;
+
+/// `size` scanners in a row, where every scanner shares at least twelve beacons with the next.
+pub fn day19(rng: &mut Rng, size: usize) -> String {
+    let scanners = (0..size as i64)
+        .map(|i| {
+            Point::new(
+                (i * SCANNER_RANGE + rng.range(-100..=100)) as i32,
+                rng.range(-150..=150) as i32,
+                rng.range(-150..=150) as i32,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let mut beacons = BTreeSet::new();
+    let place = |rng: &mut Rng, a: Point, b: Point| {
+        let mut coordinate = |a: i32, b: i32| {
+            let (a, b) = (a as i64, b as i64);
+            rng.range(a.max(b) - SCANNER_RANGE..=a.min(b) + SCANNER_RANGE) as i32
+        };
+
+        Point::new(
+            coordinate(a.x, b.x),
+            coordinate(a.y, b.y),
+            coordinate(a.z, b.z),
+        )
+    };
+
+    for pair in scanners.windows(2) {
+        while beacons
+            .iter()
+            .filter(|&&beacon| sees(pair[0], beacon) && sees(pair[1], beacon))
+            .count()
+            < 12
+        {
+            beacons.insert(place(rng, pair[0], pair[1]));
+        }
+    }
+
+    for &scanner in &scanners {
+        for _ in 0..rng.range(5..=12) {
+            beacons.insert(place(rng, scanner, scanner));
+        }
+    }
+
+    let rotations = Rotation::all();
+
+    let reports = scanners.iter().enumerate().map(|(id, &scanner)| {
+        let rotation = *rng.choose(&rotations);
+        let mut seen = beacons
+            .iter()
+            .filter(|&&beacon| sees(scanner, beacon))
+            .map(|&beacon| rotation.apply(beacon - scanner))
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut seen);
+
+        format!(
+            "--- scanner {} ---\n{}",
+            id,
+            lines(
+                seen.iter()
+                    .map(|point| format!("{},{},{}", point.x, point.y, point.z))
+            )
+        )
+    });
+
+    reports.collect::<Vec<_>>().join("\n\n")
+}
+
+fn sees(scanner: Point, beacon: Point) -> bool {
+    let difference = beacon - scanner;
+
+    [difference.x, difference.y, difference.z]
+        .iter()
+        .all(|&coordinate| (coordinate as i64).abs() <= SCANNER_RANGE)
+}
+
+fn pixels(rng: &mut Rng, count: usize) -> Vec<bool> {
+    (0..count).map(|_| rng.chance(0.5)).collect()
+}
+
+fn render_pixels(pixels: &[bool]) -> String {
+    pixels
+        .iter()
+        .map(|&lit| if lit { '#' } else { '.' })
+        .collect()
+}
+
+/// An enhancement algorithm and a `size` by `size` image. When the algorithm lights the dark
+/// background it also darkens it again, like the real puzzle.
+pub fn day20(rng: &mut Rng, size: usize) -> String {
+    let mut algorithm = pixels(rng, 512);
+    if algorithm[0] {
+        algorithm[511] = false;
+    }
+
+    let image = lines((0..size).map(|_| render_pixels(&pixels(rng, size))));
+
+    format!("{}\n\n{}", render_pixels(&algorithm), image)
+}
+
+/// The starting positions of both players, `size` is not used.
+pub fn day21(rng: &mut Rng, _size: usize) -> String {
+    format!(
+        "Player 1 starting position: {}\nPlayer 2 starting position: {}",
+        rng.range(1..=10),
+        rng.range(1..=10)
+    )
+}
+
+fn cuboid(rng: &mut Rng, limit: RangeInclusive<i64>, extent: RangeInclusive<i64>) -> String {
+    let ranges = ["x", "y", "z"]
+        .iter()
+        .map(|axis| {
+            let min = rng.range(limit.clone());
+            let max = (min + rng.range(extent.clone())).min(*limit.end());
+            format!("{}={}..{}", axis, min, max)
+        })
+        .collect::<Vec<_>>();
+
+    ranges.join(",")
+}
+
+/// `size` reboot steps, the first half of them inside the initialisation region.
+pub fn day22(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|index| {
+        let state = if index == 0 || rng.chance(0.7) {
+            "on"
+        } else {
+            "off"
+        };
+
+        let cuboid = if index < size.div_ceil(2) {
+            cuboid(rng, -50..=50, 0..=30)
+        } else {
+            cuboid(rng, -100_000..=100_000, 1000..=50_000)
+        };
+
+        format!("{} {}", state, cuboid)
+    }))
+}
+
+/// A burrow with the amphipods shuffled over the rooms that can be organized both folded and
+/// unfolded, `size` is not used.
+pub fn day23(rng: &mut Rng, _size: usize) -> String {
+    let row = |row: &[char]| {
+        row.iter()
+            .map(|amphipod| amphipod.to_string())
+            .collect::<Vec<_>>()
+            .join("#")
+    };
+
+    // Some burrows jam the hallway, mostly once unfolded, so keep trying until one doesn't
+    loop {
+        let mut amphipods = "AABBCCDD".chars().collect::<Vec<_>>();
+        rng.shuffle(&mut amphipods);
+
+        let input = format!(
+            "#############\n#...........#\n###{}###\n  #{}#\n  #########",
+            row(&amphipods[..4]),
+            row(&amphipods[4..])
+        );
+        let burrow = day23::input_generator(&input).expect("every amphipod is in a room");
+
+        if day23::organize(&burrow).is_some() && day23::organize(&burrow.unfold()).is_some() {
+            return input;
+        }
+    }
+}
+
+fn monad_block(divisor: i64, check: i64, offset: i64) -> String {
+    format!(
+        "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z {}\nadd x {}\neql x w\neql x 0\nmul y 0\n\
+         add y 25\nmul y x\nadd y 1\nmul z y\nmul y 0\nadd y w\nadd y {}\nmul y x\nadd z y",
+        divisor, check, offset
+    )
+}
+
+/// A MONAD program checking `size` digits, rounded down to an even number. Like the real one, it
+/// pushes a digit in base 26 in half of its blocks and pops it in the other half, and only
+/// accepts numbers where the popped digit is a fixed distance from the current one.
+pub fn day24(rng: &mut Rng, size: usize) -> String {
+    let digits = (size / 2).max(1) * 2;
+    let mut pushes = digits / 2;
+    let mut stack = vec![];
+    let mut blocks = vec![];
+
+    for _ in 0..digits {
+        // Real programs never push more than a few digits at once, which keeps the search short
+        if pushes > 0 && (stack.is_empty() || (stack.len() < 4 && rng.chance(0.5))) {
+            // The check is above 9, so the digit is always pushed
+            let offset = rng.range(1..=15);
+            blocks.push(monad_block(1, rng.range(10..=15), offset));
+            stack.push(offset);
+            pushes -= 1;
+        } else {
+            // Popped digit plus offset plus check is the current digit, keeping the check below
+            // 1 never lets a pushed digit pass
+            let offset = stack.pop().expect("every pop follows a push");
+            let distance = rng.range(-8..=(offset - 1).min(8));
+            blocks.push(monad_block(26, distance - offset, rng.range(1..=15)));
+        }
+    }
+
+    blocks.join("\n")
+}
+
+/// A `size` by `size` sea floor, about half of it covered in sea cucumbers, on which they stop
+/// moving within [`STEP_LIMIT`] steps.
+pub fn day25(rng: &mut Rng, size: usize) -> String {
+    // A herd can keep going around a floor that wraps forever, so keep trying until one jams
+    loop {
+        let input = lines((0..size).map(|_| {
+            (0..size)
+                .map(|_| match rng.below(20) {
+                    0..=9 => '.',
+                    10..=14 => '>',
+                    _ => 'v',
+                })
+                .collect()
+        }));
+        let floor = day25::input_generator(&input).expect("only valid cells are generated");
+
+        if floor.steps().nth(STEP_LIMIT).is_none() {
+            return input;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn test_rng() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+
+        assert_eq!(
+            (0..10).map(|_| a.next_u64()).collect::<Vec<_>>(),
+            (0..10).map(|_| b.next_u64()).collect::<Vec<_>>()
+        );
+        assert!((0..1000).all(|_| (-3..=3).contains(&a.range(-3..=3))));
+
+        let mut items = (0..10).collect::<Vec<_>>();
+        a.shuffle(&mut items);
+        items.sort_unstable();
+        assert_eq!((0..10).collect::<Vec<_>>(), items);
+    }
+
+    #[test]
+    fn test_generate_is_seeded() {
+        assert_eq!(generate(5, 20, 1), generate(5, 20, 1));
+        assert_ne!(generate(5, 20, 1), generate(5, 20, 2));
+        assert_eq!(None, generate(26, 20, 1));
+    }
+
+    #[test]
+    fn test_generated_inputs_stay_solvable() {
+        let registry = registry();
+
+        // Seeds that used to go above the surface on day 2 or leave no CO2 scrubber rating on
+        // day 3, and one that used to jam the unfolded burrow on day 23
+        for (day, seeds) in [(2, 0..20), (3, 0..20), (23, 9..10)] {
+            for seed in seeds {
+                let input = generate(day, default_size(day), seed).unwrap();
+
+                for part in registry.parts(day) {
+                    let answer = registry.get(day, part).unwrap().run(&input).unwrap();
+
+                    assert!(
+                        !answer.to_string().starts_with('-'),
+                        "day {} seed {}",
+                        day,
+                        seed
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_generated_inputs_solve() {
+        let registry = registry();
+
+        for day in registry.days() {
+            for seed in 0..3 {
+                let input = generate(day, 6, seed).unwrap();
+
+                for part in registry.parts(day) {
+                    let solver = registry.get(day, part).unwrap();
+
+                    if let Err(error) = solver.run(&input) {
+                        panic!("day {} seed {}: {}\n{}", day, seed, error, input);
+                    }
+                }
+            }
+        }
+    }
+}
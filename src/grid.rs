@@ -0,0 +1,282 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+use crate::error::ParseError;
+
+/// A position in a grid as `(x, y)`, where `(0, 0)` is the top left cell.
+pub type Position = (usize, usize);
+
+/// Offsets of the orthogonal neighbours: north, east, south, west.
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets of the diagonal neighbours: north-east, south-east, south-west, north-west.
+const DIAGONAL: [(isize, isize); 4] = [(1, -1), (1, 1), (-1, 1), (-1, -1)];
+
+/// A dense two-dimensional grid, stored row-major.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Create a grid from row-major cells, panics if the amount of cells does not match the size.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            width * height,
+            cells.len(),
+            "cell count does not match grid size"
+        );
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Position) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        if self.contains(position) {
+            Some(&self.cells[self.index_of(position)])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        if self.contains(position) {
+            let index = self.index_of(position);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        if y < self.height {
+            Some(&self.cells[y * self.width..(y + 1) * self.width])
+        } else {
+            None
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // Guard against a zero width, `chunks` does not accept a chunk size of 0
+        self.cells.chunks(self.width.max(1)).take(self.height)
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let height = if x < self.width { self.height } else { 0 };
+
+        self.cells
+            .iter()
+            .skip(x)
+            .step_by(self.width.max(1))
+            .take(height)
+    }
+
+    /// All positions of the grid in row-major order. The iterator does not borrow the grid, so it
+    /// can be used to drive in-place updates.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+
+        (0..self.width * self.height).map(move |index| (index % width, index / width))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    /// The orthogonal neighbours of a position that lie within the grid.
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = Position> {
+        self.neighbours(position, ORTHOGONAL.iter())
+    }
+
+    /// The orthogonal and diagonal neighbours of a position that lie within the grid.
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = Position> {
+        self.neighbours(position, ORTHOGONAL.iter().chain(DIAGONAL.iter()))
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    fn neighbours(
+        &self,
+        (x, y): Position,
+        offsets: impl Iterator<Item = &'static (isize, isize)>,
+    ) -> impl Iterator<Item = Position> {
+        let (width, height) = (self.width, self.height);
+
+        offsets.filter_map(move |(dx, dy)| {
+            let neighbour_x = x.checked_add_signed(*dx).filter(|nx| *nx < width)?;
+            let neighbour_y = y.checked_add_signed(*dy).filter(|ny| *ny < height)?;
+
+            Some((neighbour_x, neighbour_y))
+        })
+    }
+
+    fn index_of(&self, (x, y): Position) -> usize {
+        y * self.width + x
+    }
+}
+
+impl Grid<u32> {
+    /// Parse a block of digits, each line being a row of the grid.
+    pub fn from_digits(input: &str) -> Result<Self, ParseError> {
+        let lines = input.lines().collect::<Vec<_>>();
+        let width = lines.first().map_or(0, |line| line.len());
+
+        let mut cells = Vec::with_capacity(width * lines.len());
+
+        for line in &lines {
+            if line.len() != width {
+                return Err(ParseError::unexpected(input, line, "rows of equal width"));
+            }
+
+            for (index, c) in line.char_indices() {
+                let digit = c.to_digit(10).ok_or_else(|| {
+                    ParseError::unexpected(input, &line[index..index + c.len_utf8()], "a digit")
+                })?;
+
+                cells.push(digit);
+            }
+        }
+
+        Ok(Self::new(width, lines.len(), cells))
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &Self::Output {
+        self.get(position)
+            .unwrap_or_else(|| panic!("position {:?} is out of bounds", position))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut Self::Output {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("position {:?} is out of bounds", position))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Grid<u32> {
+        Grid::from_digits("123\n456").unwrap()
+    }
+
+    #[test]
+    fn test_from_digits() {
+        let grid = example();
+
+        assert_eq!(3, grid.width());
+        assert_eq!(2, grid.height());
+        assert_eq!(6, grid[(2, 1)]);
+    }
+
+    #[test]
+    fn test_from_digits_errors() {
+        assert_eq!(
+            "line 2, column 2: expected a digit, found `x`",
+            Grid::from_digits("123\n4x6").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "line 2, column 1: expected rows of equal width, found `45`",
+            Grid::from_digits("123\n45").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_get_out_of_bounds() {
+        let grid = example();
+
+        assert_eq!(Some(&4), grid.get((0, 1)));
+        assert_eq!(None, grid.get((3, 0)));
+        assert_eq!(None, grid.get((0, 2)));
+    }
+
+    #[test]
+    fn test_neighbours4_at_corner() {
+        let grid = example();
+
+        let neighbours = grid.neighbours4((0, 0)).collect::<Vec<_>>();
+
+        assert_eq!(vec![(1, 0), (0, 1)], neighbours);
+    }
+
+    #[test]
+    fn test_neighbours8_in_middle() {
+        let grid = Grid::filled(3, 3, 0);
+
+        let neighbours = grid.neighbours8((1, 1)).collect::<Vec<_>>();
+
+        assert_eq!(8, neighbours.len());
+        assert!(!neighbours.contains(&(1, 1)));
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = example();
+
+        assert_eq!(Some(&[4, 5, 6][..]), grid.row(1));
+        assert_eq!(None, grid.row(2));
+        assert_eq!(vec![&2, &5], grid.column(1).collect::<Vec<_>>());
+        assert_eq!(0, grid.column(3).count());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("123\n456\n", example().to_string());
+    }
+}
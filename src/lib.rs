@@ -1,8 +1,20 @@
-#![feature(drain_filter)]
 extern crate aoc_runner;
 #[macro_use]
 extern crate aoc_runner_derive;
 
+pub mod answer;
+pub mod error;
+pub mod fixture;
+pub mod gen;
+pub mod grid;
+pub mod manifest;
+pub mod path;
+pub mod reference;
+pub mod report;
+pub mod scaffold;
+pub mod solver;
+pub mod visualize;
+
 pub mod day1;
 pub mod day2;
 pub mod day3;
@@ -18,5 +30,69 @@ pub mod day11;
 pub mod day12;
 pub mod day13;
 pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+use solver::{Registry, Solution};
+
+/// Register both parts of a day, using its `input_generator`.
+macro_rules! register_day {
+    ($registry:ident, $day:literal, $module:ident) => {
+        $registry.register(Solution::new($day, 1, $module::input_generator, |input| {
+            $module::solve_part1(input).into()
+        }));
+        $registry.register(Solution::new($day, 2, $module::input_generator, |input| {
+            $module::solve_part2(input).into()
+        }));
+    };
+    // The last day only has one part
+    ($registry:ident, $day:literal, $module:ident, part1) => {
+        $registry.register(Solution::new($day, 1, $module::input_generator, |input| {
+            $module::solve_part1(input).into()
+        }));
+    };
+}
+
+/// All implemented solutions, usable without going through the aoc-runner macros.
+pub fn registry() -> Registry {
+    let mut registry = Registry::new();
+
+    register_day!(registry, 1, day1);
+    register_day!(registry, 2, day2);
+    register_day!(registry, 3, day3);
+    register_day!(registry, 4, day4);
+    register_day!(registry, 5, day5);
+    register_day!(registry, 6, day6);
+    register_day!(registry, 7, day7);
+    register_day!(registry, 8, day8);
+    register_day!(registry, 9, day9);
+    register_day!(registry, 10, day10);
+    register_day!(registry, 11, day11);
+    register_day!(registry, 12, day12);
+    register_day!(registry, 13, day13);
+    register_day!(registry, 14, day14);
+    register_day!(registry, 15, day15);
+    register_day!(registry, 16, day16);
+    register_day!(registry, 17, day17);
+    register_day!(registry, 18, day18);
+    register_day!(registry, 19, day19);
+    register_day!(registry, 20, day20);
+    register_day!(registry, 21, day21);
+    register_day!(registry, 22, day22);
+    register_day!(registry, 23, day23);
+    register_day!(registry, 24, day24);
+    register_day!(registry, 25, day25, part1);
+
+    registry
+}
 
 aoc_lib! { year = 2021 }
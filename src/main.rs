@@ -1,7 +1,627 @@
 extern crate advent_of_code_2021;
-extern crate aoc_runner;
-extern crate aoc_runner_derive;
 
-use aoc_runner_derive::aoc_main;
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::str::FromStr;
+use std::time::Duration;
 
-aoc_main! { lib = advent_of_code_2021 }
+use advent_of_code_2021::answer::Answer;
+use advent_of_code_2021::gen::{default_size, generate};
+use advent_of_code_2021::manifest::{Manifest, DEFAULT_ANSWERS};
+use advent_of_code_2021::registry;
+use advent_of_code_2021::report::{self, input_hash, Entry};
+use advent_of_code_2021::scaffold::scaffold;
+use advent_of_code_2021::solver::Registry;
+use advent_of_code_2021::visualize::{play, visualize, write_images, ImageFormat};
+
+const USAGE: &str = "Usage: advent-of-code-2021 [OPTIONS] [DAYS]
+       advent-of-code-2021 new <DAY>
+       advent-of-code-2021 visualize [OPTIONS] <DAY>
+       advent-of-code-2021 gen [OPTIONS] <DAY>
+
+Arguments:
+  [DAYS]  A single day (`7`), a range of days (`3..9` or `3-9`) or `all` [default: all]
+
+Commands:
+  new <DAY>        Generate `src/dayN.rs` from the template, register it and create an empty input
+                   and an example to fill in
+  visualize <DAY>  Play the simulation of day 4, 9, 11 or 13 in the terminal
+  gen <DAY>        Print a random input for the day
+
+Options:
+  -p, --part <PART>    Only run the given part
+  -i, --input <PATH>   An input file, a directory with `dayN.txt` files or `-` for stdin
+                       [default: input/2021]
+  -c, --check          Compare the answers with the recorded answers
+  -a, --answers <PATH> The recorded answers to check against [default: answers/2021.toml]
+  -r, --report <FORMAT> Print a `json` or `csv` report instead of text [default: text]
+  -o, --output <DIR>   Write the visualisation as numbered PPM images instead of playing it
+  -g, --gray           Write grayscale PGM images instead of PPM images
+  -s, --size <SIZE>    How large a generated input is, e.g. the number of lines
+                       [default: about the size of the real input]
+      --seed <SEED>    The seed of a generated input [default: 0]
+  -l, --list           List the available days and parts
+  -h, --help           Print this help";
+
+const DEFAULT_INPUT: &str = "input/2021";
+
+/// How long every frame of a visualisation is shown in the terminal.
+const FRAME_DELAY: Duration = Duration::from_millis(100);
+
+#[derive(Debug, PartialEq)]
+enum Input {
+    Path(PathBuf),
+    Stdin,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+/// What to do with the selected day, the commands other than running days take a single day.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Command {
+    Run,
+    /// Generate a new day from the template.
+    New(u32),
+    Visualize(u32),
+    /// Print a random input for the day.
+    Gen(u32),
+}
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    command: Command,
+    days: Option<RangeInclusive<u32>>,
+    part: Option<u32>,
+    input: Input,
+    check: bool,
+    answers: PathBuf,
+    format: Format,
+    list: bool,
+    help: bool,
+    output: Option<PathBuf>,
+    gray: bool,
+    size: Option<usize>,
+    seed: u64,
+}
+
+fn parse_number<T: FromStr>(value: &str, what: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid {} `{}`", what, value))
+}
+
+fn parse_days(value: &str) -> Result<Option<RangeInclusive<u32>>, String> {
+    if value == "all" {
+        return Ok(None);
+    }
+
+    let range = match value.split_once("..").or_else(|| value.split_once('-')) {
+        Some((start, end)) => parse_number(start, "day")?..=parse_number(end, "day")?,
+        None => {
+            let day = parse_number(value, "day")?;
+            day..=day
+        }
+    };
+
+    if range.is_empty() {
+        return Err(format!("empty range of days `{}`", value));
+    }
+
+    Ok(Some(range))
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        command: Command::Run,
+        days: None,
+        part: None,
+        input: Input::Path(PathBuf::from(DEFAULT_INPUT)),
+        check: false,
+        answers: PathBuf::from(DEFAULT_ANSWERS),
+        format: Format::Text,
+        list: false,
+        help: false,
+        output: None,
+        gray: false,
+        size: None,
+        seed: 0,
+    };
+    let mut command = None;
+    let mut days = None;
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("missing value for `{}`", name))
+        };
+
+        match arg.as_str() {
+            "-p" | "--part" => options.part = Some(parse_number(&value(&arg)?, "part")?),
+            "-i" | "--input" => {
+                options.input = match value(&arg)?.as_str() {
+                    "-" => Input::Stdin,
+                    path => Input::Path(PathBuf::from(path)),
+                }
+            }
+            "-c" | "--check" => options.check = true,
+            "-a" | "--answers" => options.answers = PathBuf::from(value(&arg)?),
+            "-r" | "--report" => {
+                options.format = match value(&arg)?.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    "csv" => Format::Csv,
+                    format => return Err(format!("unknown report format `{}`", format)),
+                }
+            }
+            "-o" | "--output" => options.output = Some(PathBuf::from(value(&arg)?)),
+            "-g" | "--gray" => options.gray = true,
+            "-s" | "--size" => options.size = Some(parse_number(&value(&arg)?, "size")?),
+            "--seed" => options.seed = parse_number(&value(&arg)?, "seed")?,
+            "-l" | "--list" => options.list = true,
+            "-h" | "--help" => options.help = true,
+            _ if arg.starts_with('-') => return Err(format!("unknown option `{}`", arg)),
+            "new" | "visualize" | "gen" if command.is_none() && days.is_none() => {
+                command = Some(arg)
+            }
+            _ if days.is_some() => return Err(format!("unexpected argument `{}`", arg)),
+            _ => days = Some(arg),
+        }
+    }
+
+    if let Some(days) = days {
+        options.days = parse_days(&days)?;
+    }
+
+    if let Some(command) = command {
+        let day = match &options.days {
+            Some(days) if days.start() == days.end() => *days.start(),
+            _ => return Err(format!("`{}` expects a single day", command)),
+        };
+
+        options.command = match command.as_str() {
+            "new" => Command::New(day),
+            "visualize" => Command::Visualize(day),
+            _ => Command::Gen(day),
+        };
+    }
+
+    Ok(options)
+}
+
+/// Read the input for a day, either from `dayN.txt` in a directory or from the given file or stdin.
+fn read_input(input: &Input, day: u32) -> Result<String, String> {
+    let path = match input {
+        Input::Stdin => {
+            let mut buffer = String::new();
+            io::stdin()
+                .read_to_string(&mut buffer)
+                .map_err(|e| format!("could not read stdin: {}", e))?;
+
+            return Ok(buffer);
+        }
+        Input::Path(path) if path.is_dir() => path.join(format!("day{}.txt", day)),
+        Input::Path(path) => path.clone(),
+    };
+
+    fs::read_to_string(&path).map_err(|e| format!("could not read `{}`: {}", path.display(), e))
+}
+
+fn print_answer(part: u32, answer: &Answer, notes: &str) {
+    if answer.is_multiline() {
+        println!("  Part {}: {}", part, notes);
+
+        for line in answer.to_string().lines() {
+            println!("    {}", line);
+        }
+    } else {
+        println!("  Part {}: {} {}", part, answer, notes);
+    }
+}
+
+/// Describe how an answer compares to the recorded one, and whether that counts as a failure.
+fn check_answer(manifest: &Manifest, day: u32, part: u32, answer: &Answer) -> (String, bool) {
+    match (
+        manifest.check(day, part, answer),
+        manifest.expected(day, part),
+    ) {
+        (Some(true), _) => ("[correct]".to_string(), true),
+        (Some(false), Some(expected)) if expected.contains('\n') => {
+            (format!("[wrong, expected:\n{}\n]", expected), false)
+        }
+        (Some(false), expected) => (
+            format!("[wrong, expected {}]", expected.unwrap_or_default()),
+            false,
+        ),
+        (None, _) => ("[no recorded answer]".to_string(), true),
+    }
+}
+
+/// Whether the input for a day is missing from the input directory, so the day is skipped.
+fn missing_input(input: &Input, day: u32) -> bool {
+    match input {
+        Input::Path(path) if path.is_dir() => !path.join(format!("day{}.txt", day)).exists(),
+        _ => false,
+    }
+}
+
+/// Run the selected days and parts, days without a `dayN.txt` in the input directory are skipped
+/// and an input that can't be read is recorded for each of its parts.
+fn collect(options: &Options, registry: &Registry) -> Result<Vec<Entry>, String> {
+    let days = registry
+        .days()
+        .into_iter()
+        .filter(|day| options.days.as_ref().is_none_or(|days| days.contains(day)))
+        .collect::<Vec<_>>();
+
+    if days.is_empty() {
+        return Err("no solvers for the selected days".to_string());
+    }
+
+    let days = days
+        .into_iter()
+        .filter(|&day| !missing_input(&options.input, day))
+        .collect::<Vec<_>>();
+
+    if days.is_empty() {
+        return Err("no input for the selected days".to_string());
+    }
+
+    let single_input = match &options.input {
+        Input::Stdin => true,
+        Input::Path(path) => !path.is_dir(),
+    };
+
+    if single_input && days.len() > 1 {
+        return Err("an input file or stdin can only be used for a single day".to_string());
+    }
+
+    let mut entries = vec![];
+
+    for day in days {
+        let input = read_input(&options.input, day);
+
+        for part in registry.parts(day) {
+            if options.part.is_some_and(|selected| selected != part) {
+                continue;
+            }
+
+            let solver = registry.get(day, part).expect("part is registered");
+
+            entries.push(match &input {
+                Ok(input) => Entry {
+                    day,
+                    part,
+                    input_hash: Some(input_hash(input)),
+                    result: solver
+                        .run_timed(input)
+                        .map_err(|error| format!("invalid input, {}", error)),
+                },
+                Err(error) => Entry {
+                    day,
+                    part,
+                    input_hash: None,
+                    result: Err(error.clone()),
+                },
+            });
+        }
+    }
+
+    if entries.is_empty() {
+        return Err("no solvers for the selected parts".to_string());
+    }
+
+    Ok(entries)
+}
+
+/// Print the entries grouped by day, returns whether every part ran and matched its recorded answer.
+fn print_entries(entries: &[Entry], manifest: Option<&Manifest>) -> bool {
+    let mut success = true;
+    let mut current_day = None;
+
+    for entry in entries {
+        if current_day != Some(entry.day) {
+            println!("Day {}", entry.day);
+            current_day = Some(entry.day);
+        }
+
+        match &entry.result {
+            Ok(run) => {
+                let mut notes = format!(
+                    "(generator: {:.2?}, solver: {:.2?})",
+                    run.generator_time, run.solve_time
+                );
+
+                if let Some(manifest) = manifest {
+                    let (check, correct) =
+                        check_answer(manifest, entry.day, entry.part, &run.answer);
+
+                    notes = format!("{} {}", check, notes);
+                    success &= correct;
+                }
+
+                print_answer(entry.part, &run.answer, &notes);
+            }
+            Err(error) => {
+                println!("  Part {}: {}", entry.part, error);
+                success = false;
+            }
+        }
+    }
+
+    success
+}
+
+fn run(options: &Options, registry: &Registry) -> Result<bool, String> {
+    let manifest = if options.check {
+        Some(Manifest::load(&options.answers).map_err(|e| e.to_string())?)
+    } else {
+        None
+    };
+
+    let entries = collect(options, registry)?;
+
+    let report = match options.format {
+        Format::Text => return Ok(print_entries(&entries, manifest.as_ref())),
+        Format::Json => report::to_json(&entries),
+        Format::Csv => report::to_csv(&entries),
+    };
+
+    print!("{}", report);
+
+    Ok(entries
+        .iter()
+        .all(|entry| match (&entry.result, &manifest) {
+            (Err(_), _) => false,
+            (Ok(_), None) => true,
+            (Ok(run), Some(manifest)) => {
+                check_answer(manifest, entry.day, entry.part, &run.answer).1
+            }
+        }))
+}
+
+/// Play the day's visualisation in the terminal, or write its frames as images.
+fn show(options: &Options, day: u32) -> Result<(), String> {
+    let input = read_input(&options.input, day)?;
+    let frames = visualize(day, &input)
+        .ok_or_else(|| format!("no visualisation for day {}", day))?
+        .map_err(|error| format!("invalid input, {}", error))?;
+
+    match &options.output {
+        Some(dir) => {
+            let format = if options.gray {
+                ImageFormat::Pgm
+            } else {
+                ImageFormat::Ppm
+            };
+            let written = write_images(&frames, dir, format)
+                .map_err(|e| format!("could not write `{}`: {}", dir.display(), e))?;
+
+            println!("Wrote {} frames to {}", written.len(), dir.display());
+        }
+        None => play(&frames, FRAME_DELAY, &mut io::stdout().lock())
+            .map_err(|e| format!("could not write to the terminal: {}", e))?,
+    }
+
+    Ok(())
+}
+
+fn list(registry: &Registry) {
+    for day in registry.days() {
+        let parts = registry
+            .parts(day)
+            .iter()
+            .map(|part| part.to_string())
+            .collect::<Vec<_>>();
+
+        println!("Day {}: part {}", day, parts.join(", "));
+    }
+}
+
+fn main() {
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("error: {}\n\n{}", error, USAGE);
+            process::exit(2);
+        }
+    };
+
+    if options.help {
+        println!("{}", USAGE);
+        return;
+    }
+
+    match options.command {
+        Command::New(day) => match scaffold(Path::new("."), day) {
+            Ok(written) => {
+                for path in written {
+                    println!("Wrote {}", path.display());
+                }
+            }
+            Err(error) => {
+                eprintln!("error: {}", error);
+                process::exit(1);
+            }
+        },
+        Command::Visualize(day) => {
+            if let Err(error) = show(&options, day) {
+                eprintln!("error: {}", error);
+                process::exit(1);
+            }
+        }
+        Command::Gen(day) => {
+            let size = options.size.unwrap_or_else(|| default_size(day));
+
+            match generate(day, size, options.seed) {
+                Some(input) => println!("{}", input),
+                None => {
+                    eprintln!("error: no generator for day {}", day);
+                    process::exit(1);
+                }
+            }
+        }
+        Command::Run => {
+            let registry = registry();
+
+            if options.list {
+                list(&registry);
+                return;
+            }
+
+            match run(&options, &registry) {
+                Ok(true) => {}
+                Ok(false) => process::exit(1),
+                Err(error) => {
+                    eprintln!("error: {}", error);
+                    process::exit(2);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(Ok(None), parse_days("all"));
+        assert_eq!(Ok(Some(7..=7)), parse_days("7"));
+        assert_eq!(Ok(Some(3..=9)), parse_days("3..9"));
+        assert_eq!(Ok(Some(3..=9)), parse_days("3-9"));
+        assert!(parse_days("9..3").is_err());
+        assert!(parse_days("x").is_err());
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let options = parse_args(args(&["5", "-p", "2", "--input", "-"])).unwrap();
+
+        assert_eq!(Some(5..=5), options.days);
+        assert_eq!(Some(2), options.part);
+        assert_eq!(Input::Stdin, options.input);
+        assert!(!options.check);
+        assert_eq!(Format::Text, options.format);
+    }
+
+    #[test]
+    fn test_parse_args_report() {
+        let options = parse_args(args(&["--report", "json"])).unwrap();
+
+        assert_eq!(Format::Json, options.format);
+        assert!(parse_args(args(&["-r", "xml"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_new() {
+        let options = parse_args(args(&["new", "15"])).unwrap();
+
+        assert_eq!(Command::New(15), options.command);
+        assert_eq!(Some(15..=15), options.days);
+        assert!(parse_args(args(&["new"])).is_err());
+        assert!(parse_args(args(&["new", "15..16"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_visualize() {
+        let options = parse_args(args(&["visualize", "11", "-o", "frames", "--gray"])).unwrap();
+
+        assert_eq!(Command::Visualize(11), options.command);
+        assert_eq!(Some(11..=11), options.days);
+        assert_eq!(Some(PathBuf::from("frames")), options.output);
+        assert!(options.gray);
+        assert!(parse_args(args(&["visualize", "all"])).is_err());
+        assert!(parse_args(args(&["new", "visualize", "4"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_gen() {
+        let options = parse_args(args(&["gen", "12", "--size", "40", "--seed", "3"])).unwrap();
+
+        assert_eq!(Command::Gen(12), options.command);
+        assert_eq!(Some(12..=12), options.days);
+        assert_eq!(Some(40), options.size);
+        assert_eq!(3, options.seed);
+        assert!(parse_args(args(&["gen", "1..3"])).is_err());
+        assert!(parse_args(args(&["gen", "1", "-s", "many"])).is_err());
+    }
+
+    #[test]
+    fn test_check_answer() {
+        let manifest = Manifest::parse("[day1]\npart1 = 7").unwrap();
+
+        assert_eq!(
+            ("[correct]".to_string(), true),
+            check_answer(&manifest, 1, 1, &Answer::from(7))
+        );
+        assert_eq!(
+            ("[wrong, expected 7]".to_string(), false),
+            check_answer(&manifest, 1, 1, &Answer::from(8))
+        );
+        assert_eq!(
+            ("[no recorded answer]".to_string(), true),
+            check_answer(&manifest, 1, 2, &Answer::from(8))
+        );
+    }
+
+    #[test]
+    fn test_parse_args_defaults() {
+        let options = parse_args(args(&[])).unwrap();
+
+        assert_eq!(Command::Run, options.command);
+        assert_eq!(None, options.days);
+        assert_eq!(None, options.part);
+        assert_eq!(Input::Path(PathBuf::from(DEFAULT_INPUT)), options.input);
+    }
+
+    #[test]
+    fn test_parse_args_errors() {
+        assert!(parse_args(args(&["-p"])).is_err());
+        assert!(parse_args(args(&["--verbose"])).is_err());
+        assert!(parse_args(args(&["1", "2"])).is_err());
+    }
+
+    #[test]
+    fn test_read_input_from_directory() {
+        let input = read_input(&Input::Path(PathBuf::from(DEFAULT_INPUT)), 6).unwrap();
+
+        assert!(input.starts_with(|c: char| c.is_ascii_digit()));
+        assert!(std::path::Path::new(DEFAULT_INPUT).is_dir());
+    }
+
+    #[test]
+    fn test_collect_skips_missing_inputs() {
+        let registry = registry();
+        let mut options = parse_args(args(&["14..15", "-p", "1"])).unwrap();
+        let entries = collect(&options, &registry).unwrap();
+
+        assert_eq!(
+            vec![(14, 1)],
+            entries
+                .iter()
+                .map(|entry| (entry.day, entry.part))
+                .collect::<Vec<_>>()
+        );
+
+        options.days = Some(15..=15);
+        assert_eq!(
+            Err("no input for the selected days".to_string()),
+            collect(&options, &registry).map(|_| ())
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use toml::value::Table;
+use toml::Value;
+
+use crate::answer::Answer;
+use crate::solver::Registry;
+
+/// Where the answers for the inputs in `input/2021` are recorded.
+pub const DEFAULT_ANSWERS: &str = "answers/2021.toml";
+
+/// An error produced when the answers manifest cannot be read.
+#[derive(Debug)]
+pub enum ManifestError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+    /// The manifest is valid TOML, but not laid out as `[dayN]` tables with `partN` answers.
+    Invalid(String),
+}
+
+impl Display for ManifestError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "could not read answers: {}", error),
+            Self::Toml(error) => write!(f, "could not parse answers: {}", error),
+            Self::Invalid(message) => write!(f, "invalid answers: {}", message),
+        }
+    }
+}
+
+impl Error for ManifestError {}
+
+/// The recorded answers to the real puzzle inputs, keyed by day and part.
+///
+/// ```toml
+/// [day1]
+/// part1 = 1752
+/// part2 = "multi-line answers are written as strings"
+/// ```
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    answers: BTreeMap<(u32, u32), String>,
+}
+
+fn parse_key(key: &str, prefix: &str) -> Result<u32, ManifestError> {
+    key.strip_prefix(prefix)
+        .and_then(|number| number.parse().ok())
+        .ok_or_else(|| ManifestError::Invalid(format!("expected `{}N`, found `{}`", prefix, key)))
+}
+
+/// Read the `partN` answers of a single day from a table.
+fn parse_parts(
+    day: u32,
+    day_key: &str,
+    parts: &Table,
+    answers: &mut BTreeMap<(u32, u32), String>,
+) -> Result<(), ManifestError> {
+    for (part_key, answer) in parts {
+        let part = parse_key(part_key, "part")?;
+        let answer = match answer {
+            Value::Integer(value) => value.to_string(),
+            Value::String(text) => text.clone(),
+            _ => {
+                return Err(ManifestError::Invalid(format!(
+                    "`{}.{}` is not an integer or a string",
+                    day_key, part_key
+                )))
+            }
+        };
+
+        answers.insert((day, part), answer);
+    }
+
+    Ok(())
+}
+
+impl Manifest {
+    pub fn parse(s: &str) -> Result<Self, ManifestError> {
+        let value = s.parse::<Value>().map_err(ManifestError::Toml)?;
+        let mut answers = BTreeMap::new();
+
+        for (day_key, parts) in value.as_table().into_iter().flatten() {
+            let day = parse_key(day_key, "day")?;
+            let parts = parts
+                .as_table()
+                .ok_or_else(|| ManifestError::Invalid(format!("`{}` is not a table", day_key)))?;
+
+            parse_parts(day, day_key, parts, &mut answers)?;
+        }
+
+        Ok(Self { answers })
+    }
+
+    /// Parse the answers of a single day, written as `partN` keys without a `[dayN]` table.
+    pub fn parse_day(day: u32, s: &str) -> Result<Self, ManifestError> {
+        let value = s.parse::<Value>().map_err(ManifestError::Toml)?;
+        let mut answers = BTreeMap::new();
+
+        if let Some(parts) = value.as_table() {
+            parse_parts(day, &format!("day{}", day), parts, &mut answers)?;
+        }
+
+        Ok(Self { answers })
+    }
+
+    /// The parts with a recorded answer for a day.
+    pub fn parts(&self, day: u32) -> Vec<u32> {
+        self.answers
+            .keys()
+            .filter(|&&(answer_day, _)| answer_day == day)
+            .map(|&(_, part)| part)
+            .collect()
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ManifestError> {
+        Self::parse(&fs::read_to_string(path).map_err(ManifestError::Io)?)
+    }
+
+    pub fn expected(&self, day: u32, part: u32) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    /// Classify the result of running a part against the recorded answer.
+    pub fn outcome<E: Display>(&self, day: u32, part: u32, result: Result<Answer, E>) -> Outcome {
+        match result {
+            Err(error) => Outcome::Failed(error.to_string()),
+            Ok(actual) => match self.check(day, part, &actual) {
+                None => Outcome::Unrecorded,
+                Some(true) => Outcome::Correct,
+                Some(false) => Outcome::Wrong {
+                    expected: self.expected(day, part).unwrap_or_default().to_string(),
+                    actual,
+                },
+            },
+        }
+    }
+
+    /// Compare an answer against the recorded one, `None` when no answer was recorded.
+    pub fn check(&self, day: u32, part: u32, answer: &Answer) -> Option<bool> {
+        self.expected(day, part)
+            .map(|expected| expected == answer.to_string())
+    }
+}
+
+/// The result of checking a single part against its recorded answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Wrong {
+        expected: String,
+        actual: Answer,
+    },
+    /// The part ran, but there is no recorded answer to compare with.
+    Unrecorded,
+    /// There is no input file for the day, so it was not run.
+    MissingInput,
+    Failed(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verification {
+    pub day: u32,
+    pub part: u32,
+    pub outcome: Outcome,
+}
+
+/// Run every registered solver against `dayN.txt` in `input_dir` and compare with the manifest.
+pub fn verify(manifest: &Manifest, registry: &Registry, input_dir: &Path) -> Vec<Verification> {
+    registry
+        .solvers()
+        .map(|solver| {
+            let (day, part) = (solver.day(), solver.part());
+            let path = input_dir.join(format!("day{}.txt", day));
+
+            let outcome = match fs::read_to_string(&path) {
+                Err(error) if error.kind() == io::ErrorKind::NotFound => Outcome::MissingInput,
+                Err(error) => Outcome::Failed(format!("{}: {}", path.display(), error)),
+                Ok(input) => manifest.outcome(day, part, solver.run(&input)),
+            };
+
+            Verification { day, part, outcome }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let manifest = Manifest::parse("[day1]\npart1 = 7\npart2 = \"\"\"\n#.\n.#\"\"\"").unwrap();
+
+        assert_eq!(Some("7"), manifest.expected(1, 1));
+        assert_eq!(Some("#.\n.#"), manifest.expected(1, 2));
+        assert_eq!(None, manifest.expected(2, 1));
+    }
+
+    #[test]
+    fn test_parse_day() {
+        let manifest = Manifest::parse_day(4, "part1 = 4512\npart2 = 1924").unwrap();
+
+        assert_eq!(Some("4512"), manifest.expected(4, 1));
+        assert_eq!(vec![1, 2], manifest.parts(4));
+        assert!(Manifest::parse_day(4, "answer = 4512").is_err());
+    }
+
+    #[test]
+    fn test_parse_invalid_key() {
+        assert!(matches!(
+            Manifest::parse("[first]\npart1 = 7"),
+            Err(ManifestError::Invalid(_))
+        ));
+        assert!(matches!(
+            Manifest::parse("[day1]\npart1 = 7.5"),
+            Err(ManifestError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_check() {
+        let manifest = Manifest::parse("[day6]\npart2 = 26984457539").unwrap();
+
+        assert_eq!(
+            Some(true),
+            manifest.check(6, 2, &Answer::from(26984457539usize))
+        );
+        assert_eq!(Some(false), manifest.check(6, 2, &Answer::from(5934)));
+        assert_eq!(None, manifest.check(6, 1, &Answer::from(5934)));
+    }
+}
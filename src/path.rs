@@ -0,0 +1,176 @@
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+use crate::grid::{Grid, Position};
+
+/// The cheapest path found by a search, from the start node to the goal node inclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N> {
+    pub cost: u64,
+    pub nodes: Vec<N>,
+}
+
+/// Find the cheapest path from `start` to a node accepted by `is_goal` with Dijkstra's algorithm,
+/// where `successors` gives the nodes reachable from a node with the cost of each step.
+pub fn dijkstra<N, S, I>(
+    start: N,
+    is_goal: impl FnMut(&N) -> bool,
+    successors: S,
+) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    astar(start, is_goal, successors, |_| 0)
+}
+
+/// Find the cheapest path like [`dijkstra`], visiting the nodes in order of their cost plus the
+/// `heuristic` estimate of the remaining cost.
+///
+/// The heuristic must never overestimate the remaining cost for the path to be the cheapest one.
+pub fn astar<N, S, I>(
+    start: N,
+    mut is_goal: impl FnMut(&N) -> bool,
+    mut successors: S,
+    mut heuristic: impl FnMut(&N) -> u64,
+) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    // Nodes are stored once and referred to by index, with the cheapest known cost to reach them
+    // and the node they were reached from
+    let mut nodes = vec![(start.clone(), 0, usize::MAX)];
+    let mut indices = HashMap::from([(start.clone(), 0)]);
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&start), 0, 0))]);
+
+    while let Some(Reverse((_, cost, index))) = queue.pop() {
+        if cost > nodes[index].1 {
+            continue;
+        }
+
+        if is_goal(&nodes[index].0) {
+            let mut path = vec![];
+            let mut current = index;
+
+            while current != usize::MAX {
+                path.push(nodes[current].0.clone());
+                current = nodes[current].2;
+            }
+
+            path.reverse();
+
+            return Some(Path { cost, nodes: path });
+        }
+
+        for (successor, step) in successors(&nodes[index].0) {
+            let successor_cost = cost + step;
+
+            let successor_index = match indices.entry(successor) {
+                Entry::Vacant(entry) => {
+                    nodes.push((entry.key().clone(), successor_cost, index));
+                    *entry.insert(nodes.len() - 1)
+                }
+                Entry::Occupied(entry) => {
+                    let existing = *entry.get();
+                    if successor_cost >= nodes[existing].1 {
+                        continue;
+                    }
+
+                    nodes[existing].1 = successor_cost;
+                    nodes[existing].2 = index;
+                    existing
+                }
+            };
+
+            let estimate = successor_cost + heuristic(&nodes[successor_index].0);
+            queue.push(Reverse((estimate, successor_cost, successor_index)));
+        }
+    }
+
+    None
+}
+
+/// Find the cheapest path between two positions of a grid moving horizontally and vertically,
+/// where `cost` gives the cost of entering a cell or `None` when it can't be entered.
+pub fn grid_path<T>(
+    grid: &Grid<T>,
+    start: Position,
+    goal: Position,
+    cost: impl Fn(&T) -> Option<u64>,
+) -> Option<Path<Position>> {
+    // Every remaining step costs at least the cheapest cell, which keeps the estimate admissible
+    let cheapest = grid.values().filter_map(&cost).min().unwrap_or(0);
+
+    astar(
+        start,
+        |&position| position == goal,
+        |&position| {
+            grid.neighbours4(position)
+                .filter_map(|neighbour| cost(&grid[neighbour]).map(|cost| (neighbour, cost)))
+                .collect::<Vec<_>>()
+        },
+        |&(x, y)| (x.abs_diff(goal.0) + y.abs_diff(goal.1)) as u64 * cheapest,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dijkstra() {
+        // A line of numbers where stepping by one costs 3 and doubling costs 1
+        let path = dijkstra(
+            1u64,
+            |&n| n == 12,
+            |&n| {
+                vec![(n + 1, 3), (n * 2, 1)]
+                    .into_iter()
+                    .filter(|&(n, _)| n <= 12)
+            },
+        )
+        .unwrap();
+
+        assert_eq!(6, path.cost);
+        assert_eq!(vec![1, 2, 3, 6, 12], path.nodes);
+    }
+
+    #[test]
+    fn test_dijkstra_unreachable() {
+        assert_eq!(
+            None,
+            dijkstra(
+                1u64,
+                |&n| n == 0,
+                |&n| if n < 5 { vec![(n + 1, 1)] } else { vec![] }
+            )
+        );
+    }
+
+    #[test]
+    fn test_grid_path() {
+        let grid = Grid::from_digits("1163\n1381\n2136").unwrap();
+        let path = grid_path(&grid, (0, 0), (3, 2), |&risk| Some(risk as u64)).unwrap();
+
+        assert_eq!(13, path.cost);
+        assert_eq!((0, 0), path.nodes[0]);
+        assert_eq!(Some(&(3, 2)), path.nodes.last());
+    }
+
+    #[test]
+    fn test_grid_path_walls() {
+        let grid = Grid::from_digits("101\n101\n111").unwrap();
+        let path = grid_path(&grid, (0, 0), (2, 0), |&cell| (cell != 0).then_some(1));
+
+        assert_eq!(Some(6), path.map(|path| path.cost));
+        assert_eq!(
+            None,
+            grid_path(&grid, (0, 0), (1, 0), |&cell| (cell != 0).then_some(1))
+        );
+    }
+}
@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::iter;
+use std::ops::Range;
+
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::gen::generate;
+use crate::solver::{Registry, Solution, Solver};
+use crate::{day14, day6, day7};
+
+/// Day 6: simulate every lanternfish on its own, instead of counting the fish per timer.
+pub fn lanternfish(timers: &[usize], days: usize) -> usize {
+    let mut school = timers.to_vec();
+
+    for _ in 0..days {
+        let mut born = 0;
+
+        for timer in &mut school {
+            if *timer == 0 {
+                *timer = 6;
+                born += 1;
+            } else {
+                *timer -= 1;
+            }
+        }
+
+        school.extend(iter::repeat_n(8, born));
+    }
+
+    school.len()
+}
+
+/// Day 7: the cheapest way to align the crabs, found by trying every position between the
+/// outermost crabs instead of going by the median or the mean.
+pub fn crab_alignment(positions: &[isize], cost: impl Fn(isize) -> isize) -> isize {
+    let (min, max) = match (positions.iter().min(), positions.iter().max()) {
+        (Some(&min), Some(&max)) => (min, max),
+        _ => return 0,
+    };
+
+    (min..=max)
+        .map(|target| {
+            positions
+                .iter()
+                .map(|position| cost((position - target).abs()))
+                .sum()
+        })
+        .min()
+        .unwrap_or(0)
+}
+
+/// Day 14: grow the polymer as a string, instead of counting its pairs.
+pub fn polymer(template: &[char], rules: &HashMap<(char, char), char>, steps: usize) -> usize {
+    let mut polymer = template.to_vec();
+
+    for _ in 0..steps {
+        let mut next = Vec::with_capacity(polymer.len() * 2);
+
+        for pair in polymer.windows(2) {
+            next.push(pair[0]);
+            next.push(rules[&(pair[0], pair[1])]);
+        }
+
+        next.extend(polymer.last());
+        polymer = next;
+    }
+
+    let mut counts = HashMap::new();
+    for element in &polymer {
+        *counts.entry(element).or_insert(0) += 1;
+    }
+
+    counts.values().max().unwrap_or(&0) - counts.values().min().unwrap_or(&0)
+}
+
+/// The reference solutions, for the parts where following the puzzle literally still finishes
+/// in time.
+pub fn references() -> Registry {
+    let mut registry = Registry::new();
+
+    registry.register(Solution::new(6, 1, day6::input_generator, |timers| {
+        lanternfish(timers, 80).into()
+    }));
+    registry.register(Solution::new(7, 1, day7::input_generator, |positions| {
+        crab_alignment(positions, |distance| distance).into()
+    }));
+    registry.register(Solution::new(7, 2, day7::input_generator, |positions| {
+        crab_alignment(positions, |distance| distance * (distance + 1) / 2).into()
+    }));
+    registry.register(Solution::new(14, 1, day14::input_generator, |input| {
+        polymer(input.template(), input.rules(), 10).into()
+    }));
+
+    registry
+}
+
+/// A generated input on which a solver and its reference solution disagree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub day: u32,
+    pub part: u32,
+    pub size: usize,
+    pub seed: u64,
+    pub input: String,
+    pub expected: Result<Answer, ParseError>,
+    pub actual: Result<Answer, ParseError>,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let describe = |result: &Result<Answer, ParseError>| match result {
+            Ok(answer) => answer.to_string(),
+            Err(error) => format!("an error, {}", error),
+        };
+
+        write!(
+            f,
+            "day {} part {} with size {} and seed {}: expected {}, found {}",
+            self.day,
+            self.part,
+            self.size,
+            self.seed,
+            describe(&self.expected),
+            describe(&self.actual)
+        )
+    }
+}
+
+fn mismatch(
+    solver: &dyn Solver,
+    reference: &dyn Solver,
+    size: usize,
+    seed: u64,
+) -> Option<Mismatch> {
+    let input = generate(reference.day(), size, seed)?;
+    let expected = reference.run(&input);
+    let actual = solver.run(&input);
+
+    if expected == actual {
+        return None;
+    }
+
+    Some(Mismatch {
+        day: reference.day(),
+        part: reference.part(),
+        size,
+        seed,
+        input,
+        expected,
+        actual,
+    })
+}
+
+/// Run every reference solution and the matching solver on an input generated from each of the
+/// seeds. The first disagreement is returned for the smallest size that still shows it, so the
+/// input is as easy to debug as possible.
+pub fn compare(
+    solvers: &Registry,
+    references: &Registry,
+    size: usize,
+    seeds: Range<u64>,
+) -> Result<(), Box<Mismatch>> {
+    for reference in references.solvers() {
+        let solver = match solvers.get(reference.day(), reference.part()) {
+            Some(solver) => solver,
+            None => continue,
+        };
+
+        for seed in seeds.clone() {
+            if let Some(found) = mismatch(solver, reference, size, seed) {
+                let smallest = (1..size)
+                    .find_map(|smaller| mismatch(solver, reference, smaller, seed))
+                    .unwrap_or(found);
+
+                return Err(Box::new(smallest));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn test_lanternfish() {
+        let timers = vec![3, 4, 3, 1, 2];
+
+        assert_eq!(26, lanternfish(&timers, 18));
+        assert_eq!(5934, lanternfish(&timers, 80));
+        assert!((0..=60).all(|days| {
+            lanternfish(&timers, days) == day6::simulate_lanternfish_school(&timers, days)
+        }));
+    }
+
+    #[test]
+    fn test_crab_alignment() {
+        let positions = vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14];
+
+        assert_eq!(37, crab_alignment(&positions, |distance| distance));
+        assert_eq!(
+            168,
+            crab_alignment(&positions, |distance| distance * (distance + 1) / 2)
+        );
+    }
+
+    #[test]
+    fn test_polymer() {
+        let input =
+            day14::input_generator(include_str!("../examples/2021/day14/example.txt").trim_end())
+                .unwrap();
+
+        assert_eq!(1588, polymer(input.template(), input.rules(), 10));
+    }
+
+    #[test]
+    fn test_compare() {
+        assert_eq!(Ok(()), compare(&registry(), &references(), 30, 0..20));
+    }
+
+    #[test]
+    fn test_compare_finds_smallest_mismatch() {
+        let mut solvers = registry();
+        solvers.register(Solution::new(7, 1, day7::input_generator, |positions| {
+            positions.len().into()
+        }));
+
+        let mismatch = compare(&solvers, &references(), 30, 0..20).unwrap_err();
+
+        // A single crab is already aligned, but the broken solver says it costs 1
+        assert_eq!((7, 1, 1), (mismatch.day, mismatch.part, mismatch.size));
+        assert_eq!(Ok(Answer::from(0)), mismatch.expected);
+        assert_eq!(Ok(Answer::from(1)), mismatch.actual);
+    }
+}
@@ -0,0 +1,176 @@
+use std::fmt::Write;
+
+use crate::solver::Run;
+
+/// A single part in a report, either a run with its timings or the reason it could not run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub day: u32,
+    pub part: u32,
+    /// The hash of the input the part ran against, see [`input_hash`].
+    pub input_hash: Option<String>,
+    pub result: Result<Run, String>,
+}
+
+/// A stable hash of the input (64-bit FNV-1a) so reports show when a part ran on different input.
+pub fn input_hash(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+
+    format!("{:016x}", hash)
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+fn json_optional_string(value: Option<&str>) -> String {
+    value.map_or_else(|| "null".to_string(), json_string)
+}
+
+/// Render the entries as a JSON array, with times in nanoseconds.
+pub fn to_json(entries: &[Entry]) -> String {
+    let objects = entries
+        .iter()
+        .map(|entry| {
+            let hash = json_optional_string(entry.input_hash.as_deref());
+
+            match &entry.result {
+                Ok(run) => format!(
+                    "  {{\"day\": {}, \"part\": {}, \"answer\": {}, \"generator_ns\": {}, \"solve_ns\": {}, \"input_hash\": {}}}",
+                    entry.day,
+                    entry.part,
+                    json_string(&run.answer.to_string()),
+                    run.generator_time.as_nanos(),
+                    run.solve_time.as_nanos(),
+                    hash
+                ),
+                Err(error) => format!(
+                    "  {{\"day\": {}, \"part\": {}, \"error\": {}, \"input_hash\": {}}}",
+                    entry.day,
+                    entry.part,
+                    json_string(error),
+                    hash
+                ),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if objects.is_empty() {
+        "[]\n".to_string()
+    } else {
+        format!("[\n{}\n]\n", objects.join(",\n"))
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Render the entries as CSV with a header row, with times in nanoseconds.
+pub fn to_csv(entries: &[Entry]) -> String {
+    let mut csv = String::from("day,part,answer,generator_ns,solve_ns,input_hash,error\n");
+
+    for entry in entries {
+        let hash = entry.input_hash.as_deref().unwrap_or_default();
+
+        let row = match &entry.result {
+            Ok(run) => format!(
+                "{},{},{},{},{},{},",
+                entry.day,
+                entry.part,
+                csv_field(&run.answer.to_string()),
+                run.generator_time.as_nanos(),
+                run.solve_time.as_nanos(),
+                hash
+            ),
+            Err(error) => format!(
+                "{},{},,,,{},{}",
+                entry.day,
+                entry.part,
+                hash,
+                csv_field(error)
+            ),
+        };
+
+        csv.push_str(&row);
+        csv.push('\n');
+    }
+
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::answer::Answer;
+
+    fn entries() -> Vec<Entry> {
+        vec![
+            Entry {
+                day: 13,
+                part: 2,
+                input_hash: Some(input_hash("6,10")),
+                result: Ok(Run {
+                    answer: Answer::from("#\"#\n##"),
+                    generator_time: Duration::from_micros(15),
+                    solve_time: Duration::from_nanos(250),
+                }),
+            },
+            Entry {
+                day: 14,
+                part: 1,
+                input_hash: None,
+                result: Err("could not read `day14.txt`, missing".to_string()),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_input_hash() {
+        assert_eq!("cbf29ce484222325", input_hash(""));
+        assert_eq!("af63dc4c8601ec8c", input_hash("a"));
+    }
+
+    #[test]
+    fn test_to_json() {
+        let expected = r##"[
+  {"day": 13, "part": 2, "answer": "#\"#\n##", "generator_ns": 15000, "solve_ns": 250, "input_hash": "0041bbea1791e0ae"},
+  {"day": 14, "part": 1, "error": "could not read `day14.txt`, missing", "input_hash": null}
+]
+"##;
+
+        assert_eq!(expected, to_json(&entries()));
+        assert_eq!("[]\n", to_json(&[]));
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let expected = "day,part,answer,generator_ns,solve_ns,input_hash,error\n13,2,\"#\"\"#\n##\",15000,250,0041bbea1791e0ae,\n14,1,,,,,\"could not read `day14.txt`, missing\"\n";
+
+        assert_eq!(expected, to_csv(&entries()));
+    }
+}
@@ -0,0 +1,294 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::fixture::DEFAULT_FIXTURES;
+
+/// The module every new day starts from, with `day0` in place of the day.
+const TEMPLATE: &str = include_str!("empty.rs");
+
+/// The example every new day starts with, which the template solves.
+const EXAMPLE_INPUT: &str = "0\n";
+const EXAMPLE_ANSWERS: &str =
+    "# Replace with the puzzle's example and its answers\npart1 = 0\npart2 = 0\n";
+
+/// An error produced when the files for a new day cannot be generated.
+#[derive(Debug)]
+pub enum ScaffoldError {
+    InvalidDay(u32),
+    /// The module for the day already exists, it is never overwritten.
+    Exists(PathBuf),
+    /// `lib.rs` could not be updated, either because the day is already in it or because it has no
+    /// days to insert the new one next to.
+    Unregistrable(String),
+    Io(PathBuf, io::Error),
+}
+
+impl Display for ScaffoldError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidDay(day) => write!(f, "invalid day {}, expected 1 to 25", day),
+            Self::Exists(path) => write!(f, "`{}` already exists", path.display()),
+            Self::Unregistrable(message) => write!(f, "could not update lib.rs: {}", message),
+            Self::Io(path, error) => write!(f, "could not write `{}`: {}", path.display(), error),
+        }
+    }
+}
+
+impl Error for ScaffoldError {}
+
+/// The source of the module for a day, made from the template.
+pub fn render_module(day: u32) -> String {
+    TEMPLATE.replace("day0", &format!("day{}", day))
+}
+
+fn parse_module_line(line: &str) -> Option<u32> {
+    line.strip_prefix("pub mod day")?
+        .strip_suffix(';')?
+        .parse()
+        .ok()
+}
+
+fn parse_register_line(line: &str) -> Option<u32> {
+    line.trim_start()
+        .strip_prefix("register_day!(registry, ")?
+        .split(',')
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// Insert a line for the day after the lines of the days before it, indented like its neighbour.
+fn insert_sorted(
+    lines: &mut Vec<String>,
+    day: u32,
+    parse: fn(&str) -> Option<u32>,
+    line: &str,
+) -> Result<(), ScaffoldError> {
+    let days = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| parse(line).map(|day| (index, day)))
+        .collect::<Vec<_>>();
+
+    if days.iter().any(|&(_, existing)| existing == day) {
+        return Err(ScaffoldError::Unregistrable(format!(
+            "day{} is already registered",
+            day
+        )));
+    }
+
+    let (neighbour, index) = match days.iter().rev().find(|&&(_, existing)| existing < day) {
+        Some(&(index, _)) => (index, index + 1),
+        None => match days.first() {
+            Some(&(index, _)) => (index, index),
+            None => {
+                return Err(ScaffoldError::Unregistrable(format!(
+                    "no line like `{}` to insert after",
+                    line.trim()
+                )))
+            }
+        },
+    };
+
+    let neighbour = &lines[neighbour];
+    let indent = &neighbour[..neighbour.len() - neighbour.trim_start().len()];
+    lines.insert(index, format!("{}{}", indent, line));
+
+    Ok(())
+}
+
+/// Add the `pub mod dayN;` declaration and the registry entry for a day to the source of `lib.rs`.
+pub fn register(lib: &str, day: u32) -> Result<String, ScaffoldError> {
+    let mut lines = lib.lines().map(String::from).collect::<Vec<_>>();
+
+    insert_sorted(
+        &mut lines,
+        day,
+        parse_module_line,
+        &format!("pub mod day{};", day),
+    )?;
+    insert_sorted(
+        &mut lines,
+        day,
+        parse_register_line,
+        &format!("register_day!(registry, {}, day{});", day, day),
+    )?;
+
+    let mut source = lines.join("\n");
+    if lib.ends_with('\n') {
+        source.push('\n');
+    }
+
+    Ok(source)
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), ScaffoldError> {
+    fs::write(path, contents).map_err(|e| ScaffoldError::Io(path.to_path_buf(), e))
+}
+
+/// Create the file and its directory, returns whether it was written since an existing file is
+/// left alone.
+fn create(path: &Path, contents: &str) -> Result<bool, ScaffoldError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| ScaffoldError::Io(parent.to_path_buf(), e))?;
+    }
+
+    match OpenOptions::new().write(true).create_new(true).open(path) {
+        Ok(mut file) => file
+            .write_all(contents.as_bytes())
+            .map(|_| true)
+            .map_err(|e| ScaffoldError::Io(path.to_path_buf(), e)),
+        Err(error) if error.kind() == io::ErrorKind::AlreadyExists => Ok(false),
+        Err(error) => Err(ScaffoldError::Io(path.to_path_buf(), error)),
+    }
+}
+
+/// Generate `src/dayN.rs`, register it in `src/lib.rs`, create an empty `input/2021/dayN.txt` and
+/// an `examples/2021/dayN/example.txt` fixture with its `.toml` answers below `root`, returning the
+/// files that were written.
+///
+/// Nothing is written when the module already exists or `lib.rs` can't be updated, and an input or
+/// example file that already exists is left alone.
+pub fn scaffold(root: &Path, day: u32) -> Result<Vec<PathBuf>, ScaffoldError> {
+    if !(1..=25).contains(&day) {
+        return Err(ScaffoldError::InvalidDay(day));
+    }
+
+    let module = root.join("src").join(format!("day{}.rs", day));
+    let lib = root.join("src").join("lib.rs");
+    let input = root.join("input/2021").join(format!("day{}.txt", day));
+    let examples = root.join(DEFAULT_FIXTURES).join(format!("day{}", day));
+
+    if module.exists() {
+        return Err(ScaffoldError::Exists(module));
+    }
+
+    let source = fs::read_to_string(&lib).map_err(|e| ScaffoldError::Io(lib.clone(), e))?;
+    let source = register(&source, day)?;
+
+    write(&module, &render_module(day))?;
+    write(&lib, &source)?;
+
+    let mut written = vec![module, lib];
+
+    for (path, contents) in [
+        (input, ""),
+        (examples.join("example.txt"), EXAMPLE_INPUT),
+        (examples.join("example.toml"), EXAMPLE_ANSWERS),
+    ] {
+        if create(&path, contents)? {
+            written.push(path);
+        }
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::Fixture;
+
+    const LIB: &str = r#"pub mod day1;
+pub mod day2;
+
+pub mod day10;
+
+pub fn registry() -> Registry {
+    let mut registry = Registry::new();
+
+    register_day!(registry, 1, day1);
+    register_day!(registry, 2, day2);
+    register_day!(registry, 10, day10, raw);
+
+    registry
+}
+"#;
+
+    #[test]
+    fn test_render_module() {
+        let module = render_module(15);
+
+        assert!(module.contains("#[aoc_generator(day15)]"));
+        assert!(module.contains("#[aoc(day15, part2)]"));
+        assert!(!module.contains("day0"));
+    }
+
+    #[test]
+    fn test_register() {
+        let expected = r#"pub mod day1;
+pub mod day2;
+pub mod day3;
+
+pub mod day10;
+
+pub fn registry() -> Registry {
+    let mut registry = Registry::new();
+
+    register_day!(registry, 1, day1);
+    register_day!(registry, 2, day2);
+    register_day!(registry, 3, day3);
+    register_day!(registry, 10, day10, raw);
+
+    registry
+}
+"#;
+
+        assert_eq!(expected, register(LIB, 3).unwrap());
+        assert!(register(LIB, 15).unwrap().contains(
+            "register_day!(registry, 10, day10, raw);\n    register_day!(registry, 15, day15);"
+        ));
+    }
+
+    #[test]
+    fn test_register_existing_day() {
+        assert!(matches!(
+            register(LIB, 10),
+            Err(ScaffoldError::Unregistrable(_))
+        ));
+        assert!(matches!(
+            register("", 10),
+            Err(ScaffoldError::Unregistrable(_))
+        ));
+    }
+
+    #[test]
+    fn test_scaffold() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/lib.rs"), LIB).unwrap();
+
+        let written = scaffold(&root, 3).unwrap();
+
+        assert_eq!(5, written.len());
+        assert_eq!(
+            render_module(3),
+            fs::read_to_string(root.join("src/day3.rs")).unwrap()
+        );
+        assert_eq!(
+            "",
+            fs::read_to_string(root.join("input/2021/day3.txt")).unwrap()
+        );
+
+        let fixture = Fixture::load(3, &root.join("examples/2021/day3/example.txt")).unwrap();
+
+        assert_eq!(EXAMPLE_INPUT, fixture.input);
+        assert_eq!(
+            (Some("0"), Some("0")),
+            (
+                fixture.answers.expected(3, 1),
+                fixture.answers.expected(3, 2)
+            )
+        );
+        assert!(matches!(scaffold(&root, 3), Err(ScaffoldError::Exists(_))));
+        assert!(matches!(
+            scaffold(&root, 26),
+            Err(ScaffoldError::InvalidDay(26))
+        ));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,241 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
+
+use crate::answer::Answer;
+use crate::error::ParseError;
+
+/// The outcome of running a solver, with the time spent parsing and solving measured separately.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    pub answer: Answer,
+    pub generator_time: Duration,
+    pub solve_time: Duration,
+}
+
+/// The input the generators expect: input files may use CRLF line endings and end with a newline,
+/// which none of the generators handle.
+pub fn normalize_input(input: &str) -> String {
+    input
+        .replace("\r\n", "\n")
+        .trim_end_matches('\n')
+        .to_string()
+}
+
+/// A single part of a day's puzzle that can be run against raw puzzle input.
+pub trait Solver: Sync {
+    fn day(&self) -> u32;
+
+    fn part(&self) -> u32;
+
+    /// Parse the input and solve it, timing both steps.
+    fn run_timed(&self, input: &str) -> Result<Run, ParseError>;
+
+    fn run(&self, input: &str) -> Result<Answer, ParseError> {
+        self.run_timed(input).map(|run| run.answer)
+    }
+}
+
+/// A solver made from a day's input generator and one of its solve functions.
+pub struct Solution<I> {
+    day: u32,
+    part: u32,
+    generator: fn(&str) -> Result<I, ParseError>,
+    solve: fn(&I) -> Answer,
+}
+
+impl<I> Solution<I> {
+    pub fn new(
+        day: u32,
+        part: u32,
+        generator: fn(&str) -> Result<I, ParseError>,
+        solve: fn(&I) -> Answer,
+    ) -> Self {
+        Self {
+            day,
+            part,
+            generator,
+            solve,
+        }
+    }
+}
+
+impl<I> Solver for Solution<I> {
+    fn day(&self) -> u32 {
+        self.day
+    }
+
+    fn part(&self) -> u32 {
+        self.part
+    }
+
+    fn run_timed(&self, input: &str) -> Result<Run, ParseError> {
+        let input = normalize_input(input);
+
+        let start = Instant::now();
+        let parsed = (self.generator)(&input)?;
+        let generated = Instant::now();
+        let answer = (self.solve)(&parsed);
+        let solved = Instant::now();
+
+        Ok(Run {
+            answer,
+            generator_time: generated - start,
+            solve_time: solved - generated,
+        })
+    }
+}
+
+/// An error produced when running a solver through the [`Registry`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunError {
+    UnknownSolver { day: u32, part: u32 },
+    Parse(ParseError),
+}
+
+impl Display for RunError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownSolver { day, part } => {
+                write!(f, "no solver for day {} part {}", day, part)
+            }
+            Self::Parse(error) => write!(f, "invalid input, {}", error),
+        }
+    }
+}
+
+impl Error for RunError {}
+
+impl From<ParseError> for RunError {
+    fn from(error: ParseError) -> Self {
+        Self::Parse(error)
+    }
+}
+
+/// A collection of solvers, looked up by day and part.
+#[derive(Default)]
+pub struct Registry {
+    solvers: Vec<Box<dyn Solver>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a solver, replacing any solver that was registered for the same day and part.
+    pub fn register(&mut self, solver: impl Solver + 'static) {
+        self.solvers
+            .retain(|existing| (existing.day(), existing.part()) != (solver.day(), solver.part()));
+        self.solvers.push(Box::new(solver));
+        self.solvers
+            .sort_by_key(|solver| (solver.day(), solver.part()));
+    }
+
+    pub fn get(&self, day: u32, part: u32) -> Option<&dyn Solver> {
+        self.solvers
+            .iter()
+            .find(|solver| solver.day() == day && solver.part() == part)
+            .map(|solver| solver.as_ref())
+    }
+
+    /// All solvers, ordered by day and part.
+    pub fn solvers(&self) -> impl Iterator<Item = &dyn Solver> {
+        self.solvers.iter().map(|solver| solver.as_ref())
+    }
+
+    pub fn days(&self) -> Vec<u32> {
+        let mut days = self
+            .solvers()
+            .map(|solver| solver.day())
+            .collect::<Vec<_>>();
+        days.dedup();
+        days
+    }
+
+    pub fn parts(&self, day: u32) -> Vec<u32> {
+        self.solvers()
+            .filter(|solver| solver.day() == day)
+            .map(|solver| solver.part())
+            .collect()
+    }
+
+    pub fn run(&self, day: u32, part: u32, input: &str) -> Result<Answer, RunError> {
+        let solver = self
+            .get(day, part)
+            .ok_or(RunError::UnknownSolver { day, part })?;
+
+        Ok(solver.run(input)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn test_registry_lists_days() {
+        let registry = registry();
+
+        assert_eq!((1..=25).collect::<Vec<_>>(), registry.days());
+        assert_eq!(vec![1, 2], registry.parts(7));
+    }
+
+    #[test]
+    fn test_registry_run() {
+        let input = "16,1,2,0,4,2,7,1,2,14\n";
+
+        assert_eq!(Ok(Answer::from(37)), registry().run(7, 1, input));
+        assert_eq!(
+            Err(RunError::UnknownSolver { day: 7, part: 3 }),
+            registry().run(7, 3, input)
+        );
+    }
+
+    #[test]
+    fn test_registry_run_crlf() {
+        let crlf = |input: &str| input.replace('\n', "\r\n");
+
+        assert_eq!(
+            Ok(Answer::from(5934)),
+            registry().run(6, 1, "3,4,3,1,2\r\n")
+        );
+        assert_eq!(
+            Ok(Answer::from(17)),
+            registry().run(
+                13,
+                1,
+                &crlf(include_str!("../examples/2021/day13/example.txt"))
+            )
+        );
+        assert_eq!(
+            Ok(Answer::from(4512)),
+            registry().run(
+                4,
+                1,
+                &crlf(include_str!("../examples/2021/day4/example.txt"))
+            )
+        );
+    }
+
+    #[test]
+    fn test_registry_run_invalid_input() {
+        let error = registry().run(7, 1, "16,x").unwrap_err();
+
+        assert_eq!(
+            "invalid input, line 1, column 4: expected a number, found `x`",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn test_register_replaces_existing() {
+        let mut registry = Registry::new();
+        registry.register(Solution::new(1, 1, |_| Ok(()), |_| Answer::from(1)));
+        registry.register(Solution::new(1, 1, |_| Ok(()), |_| Answer::from(2)));
+
+        assert_eq!(1, registry.solvers().count());
+        assert_eq!(Ok(Answer::from(2)), registry.run(1, 1, ""));
+    }
+}
@@ -0,0 +1,189 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::solver::normalize_input;
+use crate::{day11, day13, day4, day9};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+    pub const RED: Color = Color::rgb(205, 49, 49);
+    pub const GREEN: Color = Color::rgb(13, 188, 121);
+    pub const YELLOW: Color = Color::rgb(229, 229, 16);
+    pub const BLUE: Color = Color::rgb(36, 114, 200);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    pub const fn gray(level: u8) -> Self {
+        Self::rgb(level, level, level)
+    }
+
+    /// The brightness of the colour as seen by the eye, used for grayscale output.
+    pub fn luminance(&self) -> u8 {
+        ((299 * self.r as u32 + 587 * self.g as u32 + 114 * self.b as u32) / 1000) as u8
+    }
+}
+
+/// A single picture of a simulation, one colour per cell.
+pub type Frame = Grid<Color>;
+
+/// A simulation that can be watched step by step.
+pub trait Visualize {
+    /// The state before the first step followed by the state after every step.
+    fn frames(&self) -> Vec<Frame>;
+}
+
+/// The frames for a day's puzzle input, or `None` when the day has no visualisation.
+pub fn visualize(day: u32, input: &str) -> Option<Result<Vec<Frame>, ParseError>> {
+    let input = normalize_input(input);
+    let input = input.as_str();
+
+    let frames = match day {
+        4 => day4::input_generator(input).map(|bingo| bingo.frames()),
+        9 => day9::input_generator(input).map(|cave| cave.frames()),
+        11 => day11::input_generator(input).map(|cave| day11::Octopuses(&cave).frames()),
+        13 => day13::input_generator(input).map(|input| input.frames()),
+        _ => return None,
+    };
+
+    Some(frames)
+}
+
+/// Render the frame with two terminal cells for every cell, so they come out roughly square.
+pub fn to_ansi(frame: &Frame) -> String {
+    let mut s = String::new();
+
+    for row in frame.rows() {
+        for color in row {
+            s.push_str(&format!("\x1b[48;2;{};{};{}m  ", color.r, color.g, color.b));
+        }
+
+        s.push_str("\x1b[0m\n");
+    }
+
+    s
+}
+
+/// Play the frames in the terminal, clearing the screen before each one.
+pub fn play(frames: &[Frame], delay: Duration, out: &mut impl Write) -> io::Result<()> {
+    for frame in frames {
+        write!(out, "\x1b[2J\x1b[H{}", to_ansi(frame))?;
+        out.flush()?;
+        thread::sleep(delay);
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// Binary PPM, in colour.
+    Ppm,
+    /// Binary PGM, in grayscale.
+    Pgm,
+}
+
+impl ImageFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Ppm => "ppm",
+            Self::Pgm => "pgm",
+        }
+    }
+
+    /// Encode the frame as an image with one pixel for every cell.
+    pub fn encode(&self, frame: &Frame) -> Vec<u8> {
+        let magic = match self {
+            Self::Ppm => "P6",
+            Self::Pgm => "P5",
+        };
+        let mut image =
+            format!("{}\n{} {}\n255\n", magic, frame.width(), frame.height()).into_bytes();
+
+        for color in frame.values() {
+            match self {
+                Self::Ppm => image.extend([color.r, color.g, color.b].iter()),
+                Self::Pgm => image.push(color.luminance()),
+            }
+        }
+
+        image
+    }
+}
+
+/// Write every frame to its own numbered image in the directory, creating it when needed.
+pub fn write_images(frames: &[Frame], dir: &Path, format: ImageFormat) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+
+    frames
+        .iter()
+        .enumerate()
+        .map(|(index, frame)| {
+            let path = dir.join(format!("frame{:04}.{}", index, format.extension()));
+            fs::write(&path, format.encode(frame))?;
+
+            Ok(path)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame() -> Frame {
+        Grid::new(2, 1, vec![Color::RED, Color::gray(100)])
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(
+            b"P6\n2 1\n255\n\xcd\x31\x31\x64\x64\x64".to_vec(),
+            ImageFormat::Ppm.encode(&frame())
+        );
+        assert_eq!(
+            b"P5\n2 1\n255\n\x5f\x64".to_vec(),
+            ImageFormat::Pgm.encode(&frame())
+        );
+    }
+
+    #[test]
+    fn test_play() {
+        let mut out = vec![];
+        play(&[frame(), frame()], Duration::ZERO, &mut out).unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+
+        assert_eq!(2, out.matches("\x1b[2J").count());
+        assert!(out.contains("\x1b[48;2;205;49;49m  \x1b[48;2;100;100;100m  \x1b[0m\n"));
+    }
+
+    #[test]
+    fn test_visualize() {
+        let frames = visualize(13, include_str!("../examples/2021/day13/example.txt"))
+            .unwrap()
+            .unwrap();
+
+        // The unfolded paper and the paper after each of the two folds
+        assert_eq!(3, frames.len());
+        assert_eq!((5, 7), (frames[2].width(), frames[2].height()));
+        let crlf = include_str!("../examples/2021/day13/example.txt").replace('\n', "\r\n");
+        assert_eq!(frames, visualize(13, &crlf).unwrap().unwrap());
+        assert!(visualize(1, "").is_none());
+        assert!(visualize(9, "1x").unwrap().is_err());
+    }
+}
@@ -0,0 +1,26 @@
+use std::path::Path;
+
+use advent_of_code_2021::manifest::{verify, Manifest, Outcome, DEFAULT_ANSWERS};
+use advent_of_code_2021::registry;
+
+#[test]
+fn test_real_inputs_match_recorded_answers() {
+    let manifest = Manifest::load(DEFAULT_ANSWERS).unwrap();
+    let verifications = verify(&manifest, &registry(), Path::new("input/2021"));
+
+    // Days without an input file can't be checked, anything else has to match
+    let failures = verifications
+        .iter()
+        .filter(|verification| {
+            !matches!(
+                verification.outcome,
+                Outcome::Correct | Outcome::MissingInput
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert!(failures.is_empty(), "{:#?}", failures);
+    assert!(verifications
+        .iter()
+        .any(|verification| verification.outcome == Outcome::Correct));
+}
@@ -0,0 +1,25 @@
+use std::path::Path;
+
+use advent_of_code_2021::fixture::{discover, DEFAULT_FIXTURES};
+use advent_of_code_2021::manifest::Outcome;
+use advent_of_code_2021::registry;
+
+#[test]
+fn test_examples_match_expected_answers() {
+    let registry = registry();
+    let fixtures = discover(Path::new(DEFAULT_FIXTURES)).unwrap();
+
+    let failures = fixtures
+        .iter()
+        .flat_map(|fixture| {
+            fixture
+                .verify(&registry)
+                .into_iter()
+                .filter(|verification| verification.outcome != Outcome::Correct)
+                .map(move |verification| (fixture.name.as_str(), verification))
+        })
+        .collect::<Vec<_>>();
+
+    assert!(failures.is_empty(), "{:#?}", failures);
+    assert!(!fixtures.is_empty());
+}